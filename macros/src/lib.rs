@@ -16,6 +16,8 @@ use std::num::NonZeroUsize;
 ///   beforehand. Checkers will otherwise grow it as necessary using the system
 ///   allocator directly.
 /// * `verify` - Use a custom verification function (see below).
+/// * `allow_leaks` - Permit the test to leak memory, any other violations are
+///   still reported. Cannot be combined with `verify`.
 ///
 /// # Examples
 ///
@@ -29,6 +31,30 @@ use std::num::NonZeroUsize;
 /// }
 /// ```
 ///
+/// A test function can be called from within another one, in which case it
+/// doesn't clear or verify the state collected by the outer test. Only the
+/// outermost test verifies the events, so the `verify` and `allow_leaks`
+/// attributes of a nested test have no effect and its events are verified as
+/// part of the outer test. Whatever the nested test returns, like a `Result`,
+/// is passed on to its caller:
+///
+/// ```rust
+/// #[global_allocator]
+/// static ALLOCATOR: checkers::Allocator = checkers::Allocator::system();
+///
+/// #[checkers::test]
+/// fn test_inner() {
+///     let _ = Box::new(42);
+/// }
+///
+/// #[checkers::test]
+/// fn test_outer() {
+///     let b = Box::new(42);
+///     test_inner();
+///     drop(b);
+/// }
+/// ```
+///
 /// Reserve capacity for the specified number of events up front:
 ///
 /// ```rust
@@ -62,6 +88,18 @@ use std::num::NonZeroUsize;
 ///     let _ = Box::into_raw(vec![1, 2, 3, 4, 5].into_boxed_slice());
 /// }
 /// ```
+///
+/// Permitting a test to leak memory:
+///
+/// ```rust
+/// #[global_allocator]
+/// static ALLOCATOR: checkers::Allocator = checkers::Allocator::system();
+///
+/// #[checkers::test(allow_leaks = true)]
+/// fn test_allow_leaks() {
+///     let _ = Box::into_raw(Box::new(42));
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item as syn::ItemFn);
@@ -89,92 +127,170 @@ pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
             .into();
     }
 
-    let mut capacity = NonZeroUsize::new(1024).unwrap();
-    let mut verify = None::<syn::Ident>;
-
-    for arg in args {
-        if let syn::NestedMeta::Meta(syn::Meta::NameValue(namevalue)) = arg {
-            let ident = namevalue.path.get_ident();
-            if ident.is_none() {
-                let msg = "Must have specified ident";
-                return syn::Error::new_spanned(namevalue.path, msg)
-                    .to_compile_error()
-                    .into();
+    let args = match Args::parse(args, false) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let capacity = args.capacity.get();
+    let verify = args.verify();
+
+    let result = quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() #ret {
+            let guard = checkers::__test_guard();
+
+            if !guard.is_outermost() {
+                return checkers::with_unmuted(|| #body);
             }
-            match ident.unwrap().to_string().to_lowercase().as_str() {
-                "capacity" => match &namevalue.lit {
-                    syn::Lit::Int(expr) => {
-                        capacity = match expr.base10_parse::<NonZeroUsize>() {
-                            Ok(n) => n,
-                            _ => {
-                                return syn::Error::new_spanned(
-                                    expr,
-                                    "capacity argument is not valid",
-                                )
-                                .to_compile_error()
-                                .into();
-                            }
-                        }
-                    }
-                    _ => {
-                        return syn::Error::new_spanned(
-                            namevalue,
-                            "capacity argument must be an int",
-                        )
-                        .to_compile_error()
-                        .into();
-                    }
-                },
-                "verify" => match &namevalue.lit {
-                    syn::Lit::Str(expr) => {
-                        verify = Some(match expr.parse::<syn::Ident>() {
-                            Ok(ident) => ident,
-                            Err(..) => {
-                                return syn::Error::new_spanned(
-                                    expr,
-                                    "verify argument is not valid",
-                                )
-                                .to_compile_error()
-                                .into();
-                            }
-                        });
-                    }
-                    _ => {
-                        return syn::Error::new_spanned(
-                            namevalue,
-                            "verify argument must be a string",
-                        )
-                        .to_compile_error()
-                        .into();
-                    }
-                },
-                name => {
-                    let msg = format!("Unknown attribute {} is specified", name);
-                    return syn::Error::new_spanned(namevalue.path, msg)
-                        .to_compile_error()
-                        .into();
+
+            checkers::with_state(|s| {
+                {
+                    let mut s = s.borrow_mut();
+                    s.clear();
+                    s.reserve(#capacity);
                 }
-            }
+
+                let result = checkers::with_unmuted(|| #body);
+
+                let state = &mut *s.borrow_mut();
+                #verify
+                result
+            })
         }
-    }
+    };
 
-    let capacity = capacity.get();
+    result.into()
+}
 
-    let verify = match verify {
-        Some(verify) => {
-            quote! {
-                #verify(state);
-            }
+/// Run a benchmark routine in checkers.
+///
+/// The annotated function takes a single bencher argument, like
+/// `criterion::Bencher`, and its body is used as the routine being benchmarked.
+/// The routine is first run once outside of the timed region, with events
+/// collected and verified in the same way as with
+/// [`#[checkers::test]`](macro@test). Any violations cause the benchmark to
+/// panic. The timed iterations then run while the allocator is muted, so the
+/// time reported doesn't include any of the bookkeeping performed by checkers.
+///
+/// The bencher argument is only required to have an `iter` method accepting
+/// an `FnMut` closure, so the macro doesn't depend on a particular version of
+/// criterion.
+///
+/// Note that the body of the routine is evaluated and dropped within each
+/// iteration, so values which should survive optimization need to be passed
+/// through something like `criterion::black_box` inside of it.
+///
+/// # Attributes
+///
+/// The `bench` macro has the following attributes:
+/// * `capacity` - Reserve capacity for the specified number of events
+///   before the verified run.
+/// * `verify` - Use a custom verification function, see
+///   [`#[checkers::test]`](macro@test).
+/// * `allow_leaks` - Permit the routine to leak memory, see
+///   [`#[checkers::test]`](macro@test).
+/// * `allocs` - Assert that the routine performs exactly the specified number
+///   of allocations.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use criterion::{criterion_group, criterion_main, Bencher, Criterion};
+///
+/// #[global_allocator]
+/// static ALLOCATOR: checkers::Allocator = checkers::Allocator::system();
+///
+/// #[checkers::bench(allocs = 1)]
+/// fn bench_boxed(b: &mut Bencher) {
+///     criterion::black_box(Box::new(42));
+/// }
+///
+/// fn benches(c: &mut Criterion) {
+///     c.bench_function("boxed", bench_boxed);
+/// }
+///
+/// criterion_group!(all, benches);
+/// criterion_main!(all);
+/// ```
+///
+/// Any type with a compatible `iter` method can be used as the bencher:
+///
+/// ```rust
+/// #[global_allocator]
+/// static ALLOCATOR: checkers::Allocator = checkers::Allocator::system();
+///
+/// struct Bencher;
+///
+/// impl Bencher {
+///     fn iter<O, R>(&mut self, mut routine: R)
+///     where
+///         R: FnMut() -> O,
+///     {
+///         for _ in 0..10 {
+///             routine();
+///         }
+///     }
+/// }
+///
+/// #[checkers::bench(allocs = 1)]
+/// fn bench_boxed(b: &mut Bencher) {
+///     let _ = Box::new(42);
+/// }
+///
+/// bench_boxed(&mut Bencher);
+/// ```
+#[proc_macro_attribute]
+pub fn bench(args: TokenStream, item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::ItemFn);
+    let args = syn::parse_macro_input!(args as syn::AttributeArgs);
+
+    let name = &input.sig.ident;
+    let body = &input.block;
+    let attrs = &input.attrs;
+    let vis = input.vis;
+
+    if let syn::ReturnType::Type(..) = &input.sig.output {
+        let msg = "the bench function cannot have a return value";
+        return syn::Error::new_spanned(&input.sig.output, msg)
+            .to_compile_error()
+            .into();
+    }
+
+    let (bencher, ty) = match bencher_argument(&input.sig.inputs) {
+        Some(argument) => argument,
+        None => {
+            let msg = "the bench function must accept a single bencher argument";
+            return syn::Error::new_spanned(&input.sig, msg)
+                .to_compile_error()
+                .into();
         }
-        None => quote! {
-            checkers::verify!(state);
-        },
     };
 
+    let args = match Args::parse(args, true) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let capacity = args.capacity.get();
+    let verify = args.verify();
+
+    let allocs = args.allocs.map(|allocs| {
+        quote! {
+            checkers::with_muted(|| {
+                let allocs = state.events.allocs();
+
+                if allocs != #allocs {
+                    panic!("expected {} allocations per iteration, but got {}", #allocs, allocs);
+                }
+            });
+        }
+    });
+
     let result = quote! {
-        #[test]
         #(#attrs)*
-        #vis fn #name() #ret {
+        #vis fn #name(#bencher: #ty) {
             checkers::with_state(|s| {
                 {
                     let mut s = s.borrow_mut();
@@ -182,13 +298,190 @@ pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
                     s.reserve(#capacity);
                 }
 
-                checkers::with_unmuted(|| #body);
+                checkers::with_unmuted(|| {
+                    let _ = #body;
+                });
 
                 let state = &mut *s.borrow_mut();
+                #allocs
                 #verify
             });
+
+            checkers::with_muted(|| {
+                #bencher.iter(|| {
+                    let _ = #body;
+                });
+            });
         }
     };
 
     result.into()
 }
+
+/// Extract the single bencher argument of a bench function.
+fn bencher_argument(
+    inputs: &syn::punctuated::Punctuated<syn::FnArg, syn::Token![,]>,
+) -> Option<(&syn::Ident, &syn::Type)> {
+    if inputs.len() != 1 {
+        return None;
+    }
+
+    match inputs.first()? {
+        syn::FnArg::Typed(pat_type) => match &*pat_type.pat {
+            syn::Pat::Ident(pat) => Some((&pat.ident, &*pat_type.ty)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Arguments shared by the checkers attribute macros.
+struct Args {
+    capacity: NonZeroUsize,
+    verify: Option<syn::Ident>,
+    allow_leaks: bool,
+    allocs: Option<usize>,
+}
+
+impl Args {
+    /// Parse attribute arguments. `bench` indicates if bench-only arguments
+    /// are permitted.
+    fn parse(args: syn::AttributeArgs, bench: bool) -> Result<Self, syn::Error> {
+        let mut capacity = NonZeroUsize::new(1024).unwrap();
+        let mut verify = None::<syn::Ident>;
+        let mut allow_leaks = None::<syn::MetaNameValue>;
+        let mut allocs = None::<usize>;
+
+        for arg in args {
+            if let syn::NestedMeta::Meta(syn::Meta::NameValue(namevalue)) = arg {
+                let ident = match namevalue.path.get_ident() {
+                    Some(ident) => ident,
+                    None => {
+                        let msg = "Must have specified ident";
+                        return Err(syn::Error::new_spanned(namevalue.path, msg));
+                    }
+                };
+
+                match ident.to_string().to_lowercase().as_str() {
+                    "capacity" => match &namevalue.lit {
+                        syn::Lit::Int(expr) => {
+                            capacity = match expr.base10_parse::<NonZeroUsize>() {
+                                Ok(n) => n,
+                                _ => {
+                                    return Err(syn::Error::new_spanned(
+                                        expr,
+                                        "capacity argument is not valid",
+                                    ));
+                                }
+                            }
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                namevalue,
+                                "capacity argument must be an int",
+                            ));
+                        }
+                    },
+                    "verify" => match &namevalue.lit {
+                        syn::Lit::Str(expr) => {
+                            verify = Some(match expr.parse::<syn::Ident>() {
+                                Ok(ident) => ident,
+                                Err(..) => {
+                                    return Err(syn::Error::new_spanned(
+                                        expr,
+                                        "verify argument is not valid",
+                                    ));
+                                }
+                            });
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                namevalue,
+                                "verify argument must be a string",
+                            ));
+                        }
+                    },
+                    "allow_leaks" => match &namevalue.lit {
+                        syn::Lit::Bool(expr) => {
+                            if expr.value {
+                                allow_leaks = Some(namevalue.clone());
+                            }
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                namevalue,
+                                "allow_leaks argument must be a bool",
+                            ));
+                        }
+                    },
+                    "allocs" if bench => match &namevalue.lit {
+                        syn::Lit::Int(expr) => {
+                            allocs = match expr.base10_parse::<usize>() {
+                                Ok(n) => Some(n),
+                                _ => {
+                                    return Err(syn::Error::new_spanned(
+                                        expr,
+                                        "allocs argument is not valid",
+                                    ));
+                                }
+                            }
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                namevalue,
+                                "allocs argument must be an int",
+                            ));
+                        }
+                    },
+                    name => {
+                        let msg = format!("Unknown attribute {} is specified", name);
+                        return Err(syn::Error::new_spanned(namevalue.path, msg));
+                    }
+                }
+            }
+        }
+
+        if let (Some(allow_leaks), Some(..)) = (&allow_leaks, &verify) {
+            return Err(syn::Error::new_spanned(
+                allow_leaks,
+                "allow_leaks cannot be combined with a custom verify function",
+            ));
+        }
+
+        Ok(Self {
+            capacity,
+            verify,
+            allow_leaks: allow_leaks.is_some(),
+            allocs,
+        })
+    }
+
+    /// Generate the verification step.
+    fn verify(&self) -> proc_macro2::TokenStream {
+        match &self.verify {
+            Some(verify) => {
+                quote! {
+                    #verify(state);
+                }
+            }
+            None if self.allow_leaks => quote! {
+                checkers::with_muted(|| {
+                    let mut validations = Vec::new();
+                    state.validate(&mut validations);
+                    validations.retain(|v| !matches!(v, checkers::Violation::Leaked { .. }));
+
+                    for e in &validations {
+                        eprintln!("{}", e);
+                    }
+
+                    if !validations.is_empty() {
+                        panic!("allocation checks failed");
+                    }
+                });
+            },
+            None => quote! {
+                checkers::verify!(state);
+            },
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{AllocZeroed, Event, Realloc, ReallocNull, Region, Request};
 use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::io::{self, Write as _};
 
 /// Allocator that needs to be installed.
 ///
@@ -16,6 +18,7 @@ use std::alloc::{GlobalAlloc, Layout, System};
 /// ```
 pub struct Allocator<T = System> {
     delegate: T,
+    oom_policy: OomPolicy,
 }
 
 impl<T> Allocator<T> {
@@ -28,7 +31,99 @@ impl<T> Allocator<T> {
     /// static ALLOCATOR: checkers::Allocator = checkers::Allocator::new(std::alloc::System);
     /// ```
     pub const fn new(delegate: T) -> Allocator<T> {
-        Allocator { delegate }
+        Allocator {
+            delegate,
+            oom_policy: OomPolicy::ReturnNull,
+        }
+    }
+
+    /// Configure what the allocator does when the delegate allocator fails to
+    /// allocate. Defaults to [OomPolicy::ReturnNull].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Allocator, OomPolicy};
+    ///
+    /// #[global_allocator]
+    /// static ALLOCATOR: Allocator = Allocator::system().with_oom_policy(OomPolicy::Abort);
+    /// ```
+    pub const fn with_oom_policy(mut self, oom_policy: OomPolicy) -> Allocator<T> {
+        self.oom_policy = oom_policy;
+        self
+    }
+
+    /// Access the delegate allocator.
+    ///
+    /// Allocations performed directly through the delegate bypass checkers
+    /// entirely, so they are never recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::alloc::{GlobalAlloc, Layout};
+    ///
+    /// #[global_allocator]
+    /// static ALLOCATOR: checkers::Allocator = checkers::Allocator::system();
+    ///
+    /// let layout = Layout::from_size_align(16, 8).unwrap();
+    ///
+    /// let snapshot = checkers::with(|| unsafe {
+    ///     let ptr = ALLOCATOR.delegate().alloc(layout);
+    ///     ALLOCATOR.delegate().dealloc(ptr, layout);
+    /// });
+    ///
+    /// assert!(snapshot.events.is_empty());
+    /// ```
+    pub const fn delegate(&self) -> &T {
+        &self.delegate
+    }
+
+    /// Reserve capacity for at least `cap` events in the thread-local state.
+    ///
+    /// This is the programmatic equivalent of the `capacity` argument to the
+    /// [`test`](crate::test) macro, and avoids reallocating the event buffer
+    /// while recording. It should be called before [with][crate::with], and
+    /// the reservation itself is not recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[global_allocator]
+    /// static ALLOCATOR: checkers::Allocator = checkers::Allocator::system();
+    ///
+    /// ALLOCATOR.set_capacity_hint(1024);
+    ///
+    /// checkers::with_state(|s| {
+    ///     assert!(s.borrow().events.capacity() >= 1024);
+    /// });
+    /// ```
+    pub fn set_capacity_hint(&self, cap: usize) {
+        crate::with_muted(|| {
+            crate::with_state(|s| s.borrow_mut().reserve(cap));
+        });
+    }
+
+    /// Handle a failed allocation according to the configured policy.
+    fn handle_oom(&self, layout: Layout) {
+        match self.oom_policy {
+            OomPolicy::ReturnNull => (),
+            OomPolicy::HandleAllocError => {
+                // Note: write to stderr directly, since captured output is
+                // lost when the process aborts.
+                crate::with_muted(|| {
+                    let _ = writeln!(
+                        io::stderr(),
+                        "failed to allocate {} bytes with alignment {}",
+                        layout.size(),
+                        layout.align()
+                    );
+                });
+
+                std::alloc::handle_alloc_error(layout)
+            }
+            OomPolicy::Abort => std::process::abort(),
+        }
     }
 }
 
@@ -46,6 +141,69 @@ impl Allocator<System> {
     }
 }
 
+/// The debug representation includes the delegate allocator and whether the
+/// current thread is [muted][crate::is_muted].
+///
+/// # Examples
+///
+/// ```rust
+/// let allocator = checkers::Allocator::system();
+///
+/// assert_eq!(
+///     "Allocator { delegate: System, oom_policy: ReturnNull, muted: true }",
+///     format!("{:?}", allocator)
+/// );
+///
+/// checkers::with_unmuted(|| {
+///     assert!(format!("{:?}", allocator).ends_with("muted: false }"));
+/// });
+/// ```
+impl<T> fmt::Debug for Allocator<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Allocator")
+            .field("delegate", &self.delegate)
+            .field("oom_policy", &self.oom_policy)
+            .field("muted", &crate::is_muted())
+            .finish()
+    }
+}
+
+/// What the [Allocator] does when its delegate fails to allocate.
+///
+/// In all cases the failure is recorded as an event first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OomPolicy {
+    /// Return a null pointer to the caller, which is what the delegate
+    /// allocator did.
+    ReturnNull,
+    /// Print the size and alignment of the failed allocation, and then
+    /// signal it through [`std::alloc::handle_alloc_error`].
+    ///
+    /// By default this aborts the process. Note that panicking isn't
+    /// supported, since unwinding out of a global allocator is undefined
+    /// behavior.
+    HandleAllocError,
+    /// Abort the process.
+    Abort,
+}
+
+/// Record an event in the thread-local state.
+#[allow(clippy::drop_non_drop)]
+fn record(event: Event) {
+    let rejected = crate::with_state(|s| s.borrow_mut().push(event).err());
+
+    // Note: dropping a rejected event might deallocate its backtrace, which
+    // calls back into the allocator. So it must happen while muted and after
+    // the state has been released. Without backtraces events have no drop glue.
+    if let Some(event) = rejected {
+        crate::with_muted(move || drop(event));
+    }
+}
+
 unsafe impl<T> GlobalAlloc for Allocator<T>
 where
     T: GlobalAlloc,
@@ -58,28 +216,21 @@ where
         // TODO: Consider emitting diagnostics.
         if crate::is_muted() || ptr.is_null() {
             if ptr.is_null() {
-                crate::with_state(move |s| {
-                    s.borrow_mut().events.push(Event::AllocFailed);
-                });
+                record(Event::AllocFailed);
+                self.handle_oom(layout);
             }
 
             return ptr;
         }
 
-        crate::with_state(move |s| {
-            let region = Region {
-                ptr: ptr.into(),
-                size: layout.size(),
-                align: layout.align(),
-            };
-
-            let backtrace = crate::with_muted(crate::bt::capture);
-
-            s.borrow_mut()
-                .events
-                .push(Event::Alloc(Request { region, backtrace }));
-        });
+        let region = Region {
+            ptr: ptr.into(),
+            size: layout.size(),
+            align: layout.align(),
+        };
 
+        let backtrace = crate::with_muted(crate::bt::capture);
+        record(Event::Alloc(Request { region, backtrace }));
         ptr
     }
 
@@ -90,18 +241,16 @@ where
             return;
         }
 
-        crate::with_state(move |s| {
-            let backtrace = crate::with_muted(crate::bt::capture);
-
-            s.borrow_mut().events.push(Event::Free(Request {
-                region: Region {
-                    ptr: ptr.into(),
-                    size: layout.size(),
-                    align: layout.align(),
-                },
-                backtrace,
-            }));
-        });
+        let backtrace = crate::with_muted(crate::bt::capture);
+
+        record(Event::Free(Request {
+            region: Region {
+                ptr: ptr.into(),
+                size: layout.size(),
+                align: layout.align(),
+            },
+            backtrace,
+        }));
     }
 
     unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
@@ -111,36 +260,30 @@ where
         // scenario gracefully.
         if crate::is_muted() || ptr.is_null() {
             if ptr.is_null() {
-                crate::with_state(move |s| {
-                    s.borrow_mut().events.push(Event::AllocZeroedFailed);
-                });
+                record(Event::AllocZeroedFailed);
+                self.handle_oom(layout);
             }
 
             return ptr;
         }
 
-        crate::with_state(move |s| {
-            #[cfg(feature = "zeroed")]
-            let is_zeroed = Some(crate::utils::is_zeroed_ptr(ptr, layout.size()));
-            #[cfg(not(feature = "zeroed"))]
-            let is_zeroed = None;
-
-            let backtrace = crate::with_muted(crate::bt::capture);
-
-            let request = Request {
-                region: Region {
-                    ptr: ptr.into(),
-                    size: layout.size(),
-                    align: layout.align(),
-                },
-                backtrace,
-            };
-
-            s.borrow_mut()
-                .events
-                .push(Event::AllocZeroed(AllocZeroed { is_zeroed, request }));
-        });
+        #[cfg(all(feature = "zeroed", not(feature = "no-ub")))]
+        let is_zeroed = Some(crate::utils::is_zeroed_ptr(ptr, layout.size()));
+        #[cfg(any(not(feature = "zeroed"), feature = "no-ub"))]
+        let is_zeroed = None;
 
+        let backtrace = crate::with_muted(crate::bt::capture);
+
+        let request = Request {
+            region: Region {
+                ptr: ptr.into(),
+                size: layout.size(),
+                align: layout.align(),
+            },
+            backtrace,
+        };
+
+        record(Event::AllocZeroed(AllocZeroed { is_zeroed, request }));
         ptr
     }
 
@@ -150,22 +293,25 @@ where
         if crate::is_muted() || ptr.is_null() {
             if ptr.is_null() {
                 let backtrace = crate::with_muted(crate::bt::capture);
+                record(Event::ReallocNull(ReallocNull { backtrace }));
+            }
 
-                crate::with_state(|s| {
-                    s.borrow_mut()
-                        .events
-                        .push(Event::ReallocNull(ReallocNull { backtrace }));
-                });
+            let new_ptr = self.delegate.realloc(ptr, layout, new_size);
+
+            if new_ptr.is_null() {
+                // Safety: the caller guarantees that `new_size` with the
+                // original alignment forms a valid layout.
+                self.handle_oom(Layout::from_size_align_unchecked(new_size, layout.align()));
             }
 
-            return self.delegate.realloc(ptr, layout, new_size);
+            return new_ptr;
         }
 
         // Safety Note: This needs to happen before call to `realloc`, since it
         // might deallocate it.
-        #[cfg(feature = "realloc")]
+        #[cfg(all(feature = "realloc", not(feature = "no-ub")))]
         let min_size = usize::min(layout.size(), new_size);
-        #[cfg(feature = "realloc")]
+        #[cfg(all(feature = "realloc", not(feature = "no-ub")))]
         let old_hash = crate::utils::hash_ptr(ptr, min_size);
 
         // Safety Note: Convert to pointer early to avoid relying on potentially
@@ -177,40 +323,38 @@ where
         // gracefully. Prior memory is unaltered.
         // TODO: Consider emitting diagnostics.
         if new_ptr.is_null() {
-            crate::with_state(|s| {
-                s.borrow_mut().events.push(Event::ReallocFailed);
-            });
-
+            record(Event::ReallocFailed);
+            // Safety: the caller guarantees that `new_size` with the original
+            // alignment forms a valid layout.
+            self.handle_oom(Layout::from_size_align_unchecked(new_size, layout.align()));
             return new_ptr;
         }
 
-        crate::with_state(move |s| {
-            #[cfg(feature = "realloc")]
-            let is_relocated = Some(old_hash == crate::utils::hash_ptr(new_ptr, min_size));
-            #[cfg(not(feature = "realloc"))]
-            let is_relocated = None;
-
-            let backtrace = crate::with_muted(crate::bt::capture);
-
-            let free = Region {
-                ptr: old_ptr,
-                size: layout.size(),
-                align: layout.align(),
-            };
-
-            let alloc = Region {
-                ptr: new_ptr.into(),
-                size: new_size,
-                align: layout.align(),
-            };
-
-            s.borrow_mut().events.push(Event::Realloc(Realloc {
-                is_relocated,
-                free,
-                alloc,
-                backtrace,
-            }));
-        });
+        #[cfg(all(feature = "realloc", not(feature = "no-ub")))]
+        let is_relocated = Some(old_hash == crate::utils::hash_ptr(new_ptr, min_size));
+        #[cfg(any(not(feature = "realloc"), feature = "no-ub"))]
+        let is_relocated = None;
+
+        let backtrace = crate::with_muted(crate::bt::capture);
+
+        let free = Region {
+            ptr: old_ptr,
+            size: layout.size(),
+            align: layout.align(),
+        };
+
+        let alloc = Region {
+            ptr: new_ptr.into(),
+            size: new_size,
+            align: layout.align(),
+        };
+
+        record(Event::Realloc(Realloc::new(
+            is_relocated,
+            free,
+            alloc,
+            backtrace,
+        )));
 
         new_ptr
     }
@@ -130,6 +130,55 @@ impl Event {
         }
     }
 
+    /// Test if this event is equal to another, ignoring any backtraces.
+    ///
+    /// Events don't implement [PartialEq], since captured backtraces can't be
+    /// compared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Region, Request};
+    ///
+    /// let a = Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1)));
+    /// let b = Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1)));
+    /// let c = Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1)));
+    ///
+    /// assert!(a.structurally_eq(&b));
+    /// assert!(!a.structurally_eq(&c));
+    /// assert!(AllocFailed.structurally_eq(&AllocFailed));
+    /// ```
+    pub fn structurally_eq(&self, other: &Event) -> bool {
+        match (self, other) {
+            (Self::Alloc(a), Self::Alloc(b)) | (Self::Free(a), Self::Free(b)) => {
+                a.structurally_eq(b)
+            }
+            (Self::AllocZeroed(a), Self::AllocZeroed(b)) => a.structurally_eq(b),
+            (Self::Realloc(a), Self::Realloc(b)) => a.structurally_eq(b),
+            (Self::ReallocNull(a), Self::ReallocNull(b)) => a.structurally_eq(b),
+            (Self::AllocFailed, Self::AllocFailed)
+            | (Self::AllocZeroedFailed, Self::AllocZeroedFailed)
+            | (Self::ReallocFailed, Self::ReallocFailed) => true,
+            _ => false,
+        }
+    }
+
+    /// Iterate over the regions affected by this event.
+    ///
+    /// For reallocations this is the freed region followed by the allocated
+    /// one.
+    pub(crate) fn regions(&self) -> impl Iterator<Item = Region> {
+        let (first, second) = match self {
+            Self::Alloc(request)
+            | Self::Free(request)
+            | Self::AllocZeroed(AllocZeroed { request, .. }) => (Some(request.region), None),
+            Self::Realloc(realloc) => (Some(realloc.free), Some(realloc.alloc)),
+            _ => (None, None),
+        };
+
+        first.into_iter().chain(second)
+    }
+
     /// Test if this event is an allocation which matches the specified
     /// predicate.
     ///
@@ -2,9 +2,13 @@
 //!
 //! We use a wrapper type to provide convenience methods for diagnostics.
 
-use std::{ops, slice};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::{cmp, ops, slice};
 
-use crate::{Event, Machine, Violation};
+use crate::{
+    AllocZeroed, AllocationPolicy, AllocationProfile, AllocationReport, Event, Machine, Pointer,
+    Realloc, Region, Request, SanityWarning, Violation,
+};
 
 /// Collections of events.
 ///
@@ -12,12 +16,125 @@ use crate::{Event, Machine, Violation};
 #[derive(Debug, Clone)]
 pub struct Events {
     data: Vec<Event>,
+    /// If events were dropped because a limit was reached while recording.
+    truncated: bool,
+    /// The address range `[lo, hi)` which allocations must lie within.
+    guard: Option<(Pointer, Pointer)>,
 }
 
 impl Events {
     /// Construct a new collection of allocations.
     pub const fn new() -> Self {
-        Self { data: Vec::new() }
+        Self {
+            data: Vec::new(),
+            truncated: false,
+            guard: None,
+        }
+    }
+
+    /// Construct a synthetic collection of events by calling `f` until it
+    /// returns `None`, pushing each event produced.
+    ///
+    /// This is useful for generating event histories to test a [Machine]
+    /// against.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    ///
+    /// let mut n = 0usize;
+    ///
+    /// let events = Events::simulate(|| {
+    ///     n += 1;
+    ///
+    ///     if n > 4 {
+    ///         return None;
+    ///     }
+    ///
+    ///     let request = Request::without_backtrace(Region::new((n * 0x10).into(), 0x10, 1));
+    ///     Some(Alloc(request))
+    /// });
+    ///
+    /// assert_eq!(4, events.allocs());
+    /// ```
+    pub fn simulate<F>(f: F) -> Self
+    where
+        F: FnMut() -> Option<Event>,
+    {
+        Self::from_vec(std::iter::from_fn(f).collect())
+    }
+
+    /// Construct a collection from existing events.
+    pub(crate) fn from_vec(data: Vec<Event>) -> Self {
+        Self {
+            data,
+            truncated: false,
+            guard: None,
+        }
+    }
+
+    /// Construct a collection from events derived from this one, keeping
+    /// whether it was [truncated][Events::is_truncated] and its
+    /// [region guard][Events::region_guard].
+    pub(crate) fn derive(&self, data: Vec<Event>) -> Self {
+        let mut events = Self::from_vec(data);
+        events.truncated = self.truncated;
+        events.guard = self.guard;
+        events
+    }
+
+    /// Test if recording of these events was stopped early, because the
+    /// limit passed to [with_limit][crate::with_limit] was reached.
+    ///
+    /// Any analysis of truncated events might be incomplete. Notably, regions
+    /// allocated before the limit was reached and freed after it will be
+    /// reported as leaked.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Mark the events as truncated.
+    pub(crate) fn set_truncated(&mut self, truncated: bool) {
+        self.truncated = truncated;
+    }
+
+    /// Get the address range `[lo, hi)` which allocations must lie within,
+    /// if one has been set.
+    ///
+    /// See [Events::set_region_guard] for more documentation.
+    pub fn region_guard(&self) -> Option<(Pointer, Pointer)> {
+        self.guard
+    }
+
+    /// Require that all allocations lie within the address range `[lo, hi)`.
+    ///
+    /// Once set, any allocation which doesn't is reported as a
+    /// [Violation::OutOfRegion] by every method which replays the events,
+    /// like [Events::validate] and [Events::first_violation]. Only
+    /// [Events::validate_leaks_only] ignores it, since it only reports leaks.
+    /// This is what [with_region_guard][crate::with_region_guard] uses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// events.set_region_guard(0x20.into(), 0x40.into());
+    ///
+    /// let mut errors = Vec::new();
+    /// events.validate(&mut errors);
+    ///
+    /// assert_eq!(1, errors.len());
+    /// assert!(matches!(errors[0], Violation::OutOfRegion { .. }));
+    /// ```
+    pub fn set_region_guard(&mut self, lo: Pointer, hi: Pointer) {
+        self.guard = Some((lo, hi));
     }
 
     /// Get the number of events in this collection.
@@ -50,9 +167,186 @@ impl Events {
         ops::DerefMut::deref_mut(self)
     }
 
+    /// Access the underlying slice of events, in the order they were recorded.
+    ///
+    /// This is the same as [Events::as_slice], and is useful when accessing
+    /// the events of a [Snapshot][crate::Snapshot].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[global_allocator]
+    /// static ALLOCATOR: checkers::Allocator = checkers::Allocator::system();
+    ///
+    /// let snapshot = checkers::with(|| {
+    ///     let _ = Box::new(42);
+    /// });
+    ///
+    /// let events: &[checkers::Event] = snapshot.events.events();
+    /// assert_eq!(2, events.len());
+    /// ```
+    pub fn events(&self) -> &[Event] {
+        self.as_slice()
+    }
+
+    /// Iterate over all events, calling the given closure on each event as
+    /// it's being visited.
+    ///
+    /// See [Iterator::inspect].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// let mut visited = 0;
+    /// assert_eq!(2, events.inspect(|_| visited += 1).count());
+    /// assert_eq!(2, visited);
+    /// ```
+    pub fn inspect<F>(&self, mut f: F) -> impl Iterator<Item = &Event>
+    where
+        F: FnMut(&Event),
+    {
+        self.data.iter().inspect(move |e| f(e))
+    }
+
+    /// Iterate over all events together with their index.
+    ///
+    /// This is the same as `events.iter().enumerate()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// let (index, _) = events.iter_with_index().find(|(_, e)| e.is_free_with(|_| true)).unwrap();
+    /// assert_eq!(1, index);
+    /// ```
+    pub fn iter_with_index(&self) -> impl Iterator<Item = (usize, &Event)> {
+        self.data.iter().enumerate()
+    }
+
+    /// Test if this collection of events is a prefix of `other`.
+    ///
+    /// Events are compared structurally, meaning that any backtraces are
+    /// ignored. This is useful to check that two pieces of code perform the
+    /// same initial allocations, even if what follows differs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    ///
+    /// let mut a = Events::new();
+    /// a.push(Alloc(request.clone()));
+    ///
+    /// let mut b = a.clone();
+    /// b.push(Free(request));
+    ///
+    /// assert!(a.is_prefix_of(&b));
+    /// assert!(b.is_prefix_of(&b));
+    /// assert!(!b.is_prefix_of(&a));
+    /// ```
+    pub fn is_prefix_of(&self, other: &Events) -> bool {
+        self.len() <= other.len()
+            && self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .all(|(a, b)| a.structurally_eq(b))
+    }
+
+    /// Clone the first `n` events into a new collection.
+    ///
+    /// This is useful to replay the history up to a given point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is larger than the number of events.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// let prefix = events.snapshot_at(1);
+    /// assert_eq!(1, prefix.len());
+    /// assert_eq!(0x10, prefix.max_memory_used().unwrap());
+    /// ```
+    pub fn snapshot_at(&self, n: usize) -> Events {
+        assert!(
+            n <= self.len(),
+            "snapshot index {} is out of bounds for {} events",
+            n,
+            self.len()
+        );
+
+        self.derive(self.data[..n].to_vec())
+    }
+
+    /// Sort the events by the size of their primary region in descending
+    /// order, so that the largest allocations come first.
+    ///
+    /// The primary region of a reallocation is the region it allocates.
+    /// Events without a region, like failed allocations, are sorted last. The
+    /// sort is stable, so events of the same size keep their relative order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(AllocFailed);
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x40, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// let sorted = events.into_sorted_by_size();
+    ///
+    /// assert!(sorted[0].is_alloc_with(|r| r.size == 0x40));
+    /// assert!(sorted[1].is_alloc_with(|r| r.size == 0x10));
+    /// assert!(sorted[2].is_free_with(|r| r.size == 0x10));
+    /// assert!(sorted[3].is_failed());
+    /// ```
+    pub fn into_sorted_by_size(mut self) -> Events {
+        self.data.sort_by_key(|e| {
+            let size = match e {
+                Event::Alloc(request)
+                | Event::Free(request)
+                | Event::AllocZeroed(AllocZeroed { request, .. }) => Some(request.region.size),
+                Event::Realloc(realloc) => Some(realloc.alloc.size),
+                _ => None,
+            };
+
+            cmp::Reverse(size)
+        });
+
+        self
+    }
+
     /// Clear the collection of events.
     pub fn clear(&mut self) {
         self.data.clear();
+        self.truncated = false;
+        self.guard = None;
     }
 
     /// Push a single event into the collection of events.
@@ -155,28 +449,155 @@ impl Events {
             .sum()
     }
 
-    /// Validate the current state and populate the errors collection with any
-    /// violations found.
+    /// Iterate over all failed allocations, zeroed allocations and
+    /// reallocations.
     ///
-    /// See [Machine::push] for more details on the kind of validation errors
-    /// that can be raised.
-    pub fn validate(&self, errors: &mut Vec<Violation>) {
-        let mut machine = Machine::default();
+    /// See [Event::is_failed].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(AllocFailed);
+    /// events.push(ReallocFailed);
+    ///
+    /// assert_eq!(2, events.iter_failed().count());
+    /// ```
+    pub fn iter_failed(&self) -> impl Iterator<Item = &Event> {
+        self.data.iter().filter(|e| e.is_failed())
+    }
+
+    /// Iterate over the payload of all zeroed allocations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{AllocZeroed, Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(AllocZeroed(AllocZeroed::new(
+    ///     Some(true),
+    ///     Request::without_backtrace(Region::new(0x20.into(), 0x10, 1)),
+    /// )));
+    ///
+    /// assert_eq!(1, events.iter_alloc_zeroed().count());
+    /// assert!(events.iter_alloc_zeroed().all(|z| z.is_zeroed == Some(true)));
+    /// ```
+    pub fn iter_alloc_zeroed(&self) -> impl Iterator<Item = &AllocZeroed> {
+        self.data.iter().filter_map(|e| match e {
+            Event::AllocZeroed(alloc_zeroed) => Some(alloc_zeroed),
+            _ => None,
+        })
+    }
+
+    /// Partition the events by their kind in a single pass.
+    ///
+    /// This is useful for custom verifiers which need to look at every kind
+    /// of event.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{AllocZeroed, Event::*, Events, Region, Realloc, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(AllocZeroed(AllocZeroed::new(
+    ///     Some(true),
+    ///     Request::without_backtrace(Region::new(0x20.into(), 0x10, 1)),
+    /// )));
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x10, 1),
+    ///     Region::new(0x40.into(), 0x20, 1),
+    /// )));
+    /// events.push(AllocFailed);
+    /// events.push(Free(Request::without_backtrace(Region::new(0x40.into(), 0x20, 1))));
+    ///
+    /// let collected = events.collect_by_type();
+    ///
+    /// assert_eq!(1, collected.allocs.len());
+    /// assert_eq!(1, collected.alloc_zeroed.len());
+    /// assert_eq!(1, collected.reallocs.len());
+    /// assert_eq!(1, collected.frees.len());
+    /// assert_eq!(1, collected.failed.len());
+    ///
+    /// assert_eq!(events.allocs(), collected.allocs());
+    /// assert_eq!(events.reallocs(), collected.reallocs());
+    /// assert_eq!(events.frees(), collected.frees());
+    /// ```
+    pub fn collect_by_type(&self) -> CollectedEvents<'_> {
+        let mut collected = CollectedEvents::default();
 
         for event in self.as_slice() {
-            if let Err(e) = machine.push(event) {
-                errors.push(e);
+            match event {
+                Event::Alloc(request) => collected.allocs.push(request),
+                Event::AllocZeroed(alloc_zeroed) => collected.alloc_zeroed.push(alloc_zeroed),
+                Event::Free(request) => collected.frees.push(request),
+                Event::Realloc(realloc) => collected.reallocs.push(realloc),
+                event if event.is_failed() => collected.failed.push(event),
+                _ => (),
             }
         }
 
-        for alloc in machine.trailing_regions() {
-            errors.push(Violation::Leaked { alloc });
-        }
+        collected
     }
 
-    /// Max amount of memory used according to this event history.
+    /// Get the request of the first [Alloc][Event::Alloc] or
+    /// [AllocZeroed][Event::AllocZeroed] event.
     ///
-    /// Returns the first violation encountered if the history is not sound.
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// assert!(events.first_alloc().is_none());
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// assert_eq!(Some(0x10.into()), events.first_alloc().map(|r| r.region.ptr));
+    /// assert_eq!(Some(0x20.into()), events.last_alloc().map(|r| r.region.ptr));
+    /// assert_eq!(Some(0x20.into()), events.first_free().map(|r| r.region.ptr));
+    /// assert_eq!(Some(0x10.into()), events.last_free().map(|r| r.region.ptr));
+    /// ```
+    pub fn first_alloc(&self) -> Option<&Request> {
+        self.data.iter().find_map(alloc_request)
+    }
+
+    /// Get the request of the last [Alloc][Event::Alloc] or
+    /// [AllocZeroed][Event::AllocZeroed] event.
+    ///
+    /// See [Events::first_alloc] for more documentation.
+    pub fn last_alloc(&self) -> Option<&Request> {
+        self.data.iter().rev().find_map(alloc_request)
+    }
+
+    /// Get the request of the first [Free][Event::Free] event.
+    ///
+    /// See [Events::first_alloc] for more documentation.
+    pub fn first_free(&self) -> Option<&Request> {
+        self.data.iter().find_map(free_request)
+    }
+
+    /// Get the request of the last [Free][Event::Free] event.
+    ///
+    /// See [Events::first_alloc] for more documentation.
+    pub fn last_free(&self) -> Option<&Request> {
+        self.data.iter().rev().find_map(free_request)
+    }
+
+    /// Find the largest [Alloc][Event::Alloc] or
+    /// [AllocZeroed][Event::AllocZeroed] request.
+    ///
+    /// If several allocations have the same size, the first one is returned.
     ///
     /// # Examples
     ///
@@ -184,28 +605,2691 @@ impl Events {
     /// use checkers::{Event::*, Events, Region, Request};
     /// let mut events = Events::new();
     ///
-    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
-    /// events.push(Alloc(request));
+    /// assert!(events.find_largest_alloc().is_none());
     ///
-    /// let request = Request::without_backtrace(Region::new(0x20.into(), 0x10, 1));
-    /// events.push(Alloc(request));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x40, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x80.into(), 0x08, 1))));
     ///
-    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
-    /// events.push(Free(request));
+    /// assert_eq!(Some(0x40), events.find_largest_alloc().map(|r| r.region.size));
+    /// assert_eq!(Some(0x08), events.find_smallest_alloc().map(|r| r.region.size));
+    /// ```
+    pub fn find_largest_alloc(&self) -> Option<&Request> {
+        self.data
+            .iter()
+            .rev()
+            .filter_map(alloc_request)
+            .max_by_key(|r| r.region.size)
+    }
+
+    /// Find the smallest [Alloc][Event::Alloc] or
+    /// [AllocZeroed][Event::AllocZeroed] request.
     ///
-    /// assert_eq!(0x20, events.max_memory_used().unwrap());
+    /// See [Events::find_largest_alloc] for more documentation.
+    pub fn find_smallest_alloc(&self) -> Option<&Request> {
+        self.data
+            .iter()
+            .filter_map(alloc_request)
+            .min_by_key(|r| r.region.size)
+    }
+
+    /// Count the number of allocations of exactly the given size.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 8, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 8, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x30.into(), 16, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 8, 1))));
+    ///
+    /// assert_eq!(2, events.alloc_count_for_size(8));
+    /// assert_eq!(1, events.alloc_count_for_size(16));
+    /// assert_eq!(1, events.free_count_for_size(8));
+    /// assert_eq!(0, events.free_count_for_size(16));
     /// ```
-    pub fn max_memory_used(&self) -> Result<usize, Violation> {
-        let mut machine = Machine::default();
+    pub fn alloc_count_for_size(&self, size: usize) -> usize {
+        self.data
+            .iter()
+            .filter(|e| e.is_alloc_with(|r| r.size == size))
+            .count()
+    }
 
-        let mut max = 0usize;
+    /// Count the number of deallocations of exactly the given size.
+    ///
+    /// See [Events::alloc_count_for_size] for more documentation.
+    pub fn free_count_for_size(&self, size: usize) -> usize {
+        self.data
+            .iter()
+            .filter(|e| e.is_free_with(|r| r.size == size))
+            .count()
+    }
 
-        for event in self.as_slice() {
-            machine.push(event)?;
-            max = usize::max(machine.memory_used, max);
-        }
+    /// Count the number of allocations with exactly the given alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x20, 32))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x40.into(), 0x20, 32))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x60.into(), 0x08, 8))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x20.into(), 0x20, 32))));
+    ///
+    /// assert_eq!(2, events.alloc_count_for_alignment(32));
+    /// assert_eq!(1, events.alloc_count_for_alignment(8));
+    /// assert_eq!(1, events.free_count_for_alignment(32));
+    /// assert_eq!(0, events.free_count_for_alignment(8));
+    /// ```
+    pub fn alloc_count_for_alignment(&self, align: usize) -> usize {
+        self.data
+            .iter()
+            .filter(|e| e.is_alloc_with(|r| r.align == align))
+            .count()
+    }
 
-        Ok(max)
+    /// Count the number of deallocations with exactly the given alignment.
+    ///
+    /// See [Events::alloc_count_for_alignment] for more documentation.
+    pub fn free_count_for_alignment(&self, align: usize) -> usize {
+        self.data
+            .iter()
+            .filter(|e| e.is_free_with(|r| r.align == align))
+            .count()
+    }
+
+    /// Count the number of allocations larger than the given size.
+    ///
+    /// Only [Alloc][Event::Alloc] and [AllocZeroed][Event::AllocZeroed] events
+    /// are counted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 8, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 16, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x40.into(), 32, 1))));
+    ///
+    /// assert_eq!(1, events.count_allocations_larger_than(16));
+    /// assert_eq!(1, events.count_allocations_smaller_than(16));
+    /// ```
+    pub fn count_allocations_larger_than(&self, size: usize) -> usize {
+        self.data
+            .iter()
+            .filter(|e| e.is_alloc_with(|r| r.size > size))
+            .count()
+    }
+
+    /// Count the number of allocations smaller than the given size.
+    ///
+    /// See [Events::count_allocations_larger_than] for more documentation.
+    pub fn count_allocations_smaller_than(&self, size: usize) -> usize {
+        self.data
+            .iter()
+            .filter(|e| e.is_alloc_with(|r| r.size < size))
+            .count()
+    }
+
+    /// Count the number of allocations for each alignment.
+    ///
+    /// Only [Alloc][Event::Alloc] and [AllocZeroed][Event::AllocZeroed] events
+    /// are counted, like in [Events::allocs].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 8, 8))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 8, 16))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x30.into(), 16, 16))));
+    ///
+    /// let counts = events.count_by_alignment();
+    /// assert_eq!(Some(&1), counts.get(&8));
+    /// assert_eq!(Some(&2), counts.get(&16));
+    /// assert_eq!(None, counts.get(&4));
+    /// ```
+    pub fn count_by_alignment(&self) -> BTreeMap<usize, usize> {
+        let mut counts = BTreeMap::new();
+
+        for event in self.as_slice() {
+            if let Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) = event {
+                *counts.entry(request.region.align).or_default() += 1;
+            }
+        }
+
+        counts
+    }
+
+    /// Group the requests of all allocations by their alignment.
+    ///
+    /// Only [Alloc][Event::Alloc] and [AllocZeroed][Event::AllocZeroed] events
+    /// are included, in the order they were recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x40.into(), 0x40, 64))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x90.into(), 0x10, 8))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0xc0.into(), 0x40, 64))));
+    ///
+    /// let groups = events.group_by_alignment();
+    /// assert_eq!(vec![&8, &64], groups.keys().collect::<Vec<_>>());
+    /// assert!(groups[&64].iter().all(|r| r.region.size == 0x40));
+    /// assert_eq!(2, groups[&64].len());
+    /// ```
+    pub fn group_by_alignment(&self) -> BTreeMap<usize, Vec<&Request>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+
+        for request in self.data.iter().filter_map(alloc_request) {
+            groups
+                .entry(request.region.align)
+                .or_default()
+                .push(request);
+        }
+
+        groups
+    }
+
+    /// The index of the first event which frees a region, which is the same as
+    /// the number of events preceding it.
+    ///
+    /// Note that this counts both [Free][Event::Free] and
+    /// [Realloc][Event::Realloc] events, since a reallocation frees the region
+    /// it reallocates. Returns `None` if there are no such events.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    /// assert_eq!(None, events.time_to_first_free());
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// assert_eq!(Some(1), events.time_to_first_free());
+    /// ```
+    pub fn time_to_first_free(&self) -> Option<usize> {
+        self.data
+            .iter()
+            .position(|e| matches!(e, Event::Free(..) | Event::Realloc(..)))
+    }
+
+    /// The index of the last allocation in this collection of events.
+    ///
+    /// Returns `None` if there are no allocations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    /// assert_eq!(None, events.time_to_last_alloc());
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// assert_eq!(Some(0), events.time_to_last_alloc());
+    /// ```
+    pub fn time_to_last_alloc(&self) -> Option<usize> {
+        self.data
+            .iter()
+            .rposition(|e| matches!(e, Event::Alloc(..) | Event::AllocZeroed(..)))
+    }
+
+    /// Build a histogram of the number of events between each allocation and
+    /// the deallocation which frees it.
+    ///
+    /// Short latencies indicate temporary allocations, while long latencies
+    /// indicate long-lived ones. Allocations which are never freed are
+    /// ignored, and reallocations both end the latency of the region they
+    /// free and start the latency of the region they allocate, see
+    /// [Events::iter_alloc_free_pairs].
+    ///
+    /// The latencies are divided into `buckets` buckets of equal width, and
+    /// each bucket is returned as `(max_latency, count)` where `max_latency`
+    /// is the largest latency which fits in the bucket. Returns an empty
+    /// histogram if `buckets` is zero or if nothing was freed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let a = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// let b = Request::without_backtrace(Region::new(0x20.into(), 0x10, 1));
+    /// let c = Request::without_backtrace(Region::new(0x30.into(), 0x10, 1));
+    ///
+    /// events.push(Alloc(a.clone()));
+    /// events.push(Alloc(b.clone()));
+    /// events.push(Free(b));
+    /// events.push(Alloc(c.clone()));
+    /// events.push(Free(c));
+    /// events.push(Free(a));
+    ///
+    /// assert_eq!(
+    ///     vec![(3, 2), (6, 1)],
+    ///     events.alloc_to_free_latency_distribution(2)
+    /// );
+    /// ```
+    pub fn alloc_to_free_latency_distribution(&self, buckets: usize) -> Vec<(usize, usize)> {
+        let latencies = self
+            .alloc_free_pairs()
+            .into_iter()
+            .map(|(alloc, free, _)| free - alloc)
+            .collect::<Vec<_>>();
+
+        let max = match latencies.iter().max() {
+            Some(&max) if buckets > 0 => max,
+            _ => return Vec::new(),
+        };
+
+        let width = (max + buckets - 1) / buckets;
+
+        let mut histogram = (1..=buckets).map(|n| (n * width, 0)).collect::<Vec<_>>();
+
+        for latency in latencies {
+            histogram[(latency - 1) / width].1 += 1;
+        }
+
+        histogram
+    }
+
+    /// Iterate over all matched allocations and deallocations as
+    /// `(alloc_index, free_index, request)`, in the order in which they were
+    /// deallocated.
+    ///
+    /// The request is the one of the allocation. Reallocations both free the
+    /// region they reallocate and allocate a new one, and allocations which
+    /// are never freed are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Realloc, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x10, 1),
+    ///     Region::new(0x40.into(), 0x20, 1),
+    /// )));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x40.into(), 0x20, 1))));
+    ///
+    /// let pairs = events
+    ///     .iter_alloc_free_pairs()
+    ///     .map(|(alloc, free, request)| (alloc, free, request.region))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         (0, 2, Region::new(0x10.into(), 0x10, 1)),
+    ///         (2, 3, Region::new(0x40.into(), 0x20, 1)),
+    ///     ],
+    ///     pairs
+    /// );
+    /// ```
+    pub fn iter_alloc_free_pairs(&self) -> impl Iterator<Item = (usize, usize, Request)> {
+        self.alloc_free_pairs().into_iter()
+    }
+
+    /// Collect all matched allocations and deallocations as
+    /// `(alloc_index, free_index, request)`, in the order of deallocation.
+    fn alloc_free_pairs(&self) -> Vec<(usize, usize, Request)> {
+        let mut live = HashMap::<Pointer, (usize, Request)>::new();
+        let mut pairs = Vec::new();
+
+        for (index, event) in self.data.iter().enumerate() {
+            let freed = match event {
+                Event::Free(request) => Some(request.region.ptr),
+                Event::Realloc(realloc) => Some(realloc.free.ptr),
+                _ => None,
+            };
+
+            if let Some((alloc, request)) = freed.and_then(|ptr| live.remove(&ptr)) {
+                pairs.push((alloc, index, request));
+            }
+
+            if let Some(request) = allocated_request(event) {
+                live.insert(request.region.ptr, (index, request));
+            }
+        }
+
+        pairs
+    }
+
+    /// Sum the size deltas of all reallocations in this collection of events.
+    ///
+    /// A positive value means that reallocations in total grew the regions
+    /// they operated on, while a negative value means they shrunk them. Note
+    /// that this doesn't account for regular allocations and deallocations.
+    ///
+    /// See [Realloc::size_delta][crate::Realloc::size_delta] for more
+    /// documentation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Realloc};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(10.into(), 10, 1),
+    ///     Region::new(20.into(), 40, 1)
+    /// )));
+    ///
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(20.into(), 40, 1),
+    ///     Region::new(20.into(), 20, 1)
+    /// )));
+    ///
+    /// assert_eq!(10, events.total_size_delta());
+    /// ```
+    pub fn total_size_delta(&self) -> isize {
+        self.data
+            .iter()
+            .map(|e| match e {
+                Event::Realloc(realloc) => realloc.size_delta(),
+                _ => 0,
+            })
+            .fold(0isize, isize::saturating_add)
+    }
+
+    /// The largest growth factor of any reallocation, which is the size of the
+    /// allocated region divided by the size of the freed region.
+    ///
+    /// Reallocations of zero-sized regions are ignored. Returns `None` if
+    /// there are no such reallocations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Realloc};
+    /// let mut events = Events::new();
+    ///
+    /// assert_eq!(None, events.max_realloc_factor());
+    ///
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(10.into(), 10, 1),
+    ///     Region::new(20.into(), 20, 1)
+    /// )));
+    ///
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(20.into(), 20, 1),
+    ///     Region::new(20.into(), 5, 1)
+    /// )));
+    ///
+    /// assert_eq!(Some(2.0), events.max_realloc_factor());
+    /// assert_eq!(Some(0.25), events.min_realloc_factor());
+    /// ```
+    pub fn max_realloc_factor(&self) -> Option<f64> {
+        self.realloc_factors().fold(None, |max, f| match max {
+            Some(max) if max >= f => Some(max),
+            _ => Some(f),
+        })
+    }
+
+    /// The smallest growth factor of any reallocation, values less than `1.0`
+    /// indicate that a region was shrunk.
+    ///
+    /// See [Events::max_realloc_factor] for more documentation.
+    pub fn min_realloc_factor(&self) -> Option<f64> {
+        self.realloc_factors().fold(None, |min, f| match min {
+            Some(min) if min <= f => Some(min),
+            _ => Some(f),
+        })
+    }
+
+    /// Iterate over the growth factors of all reallocations of non-empty
+    /// regions.
+    fn realloc_factors(&self) -> impl Iterator<Item = f64> + '_ {
+        self.data.iter().filter_map(|e| match e {
+            Event::Realloc(realloc) if realloc.free.size > 0 => {
+                Some(realloc.alloc.size as f64 / realloc.free.size as f64)
+            }
+            _ => None,
+        })
+    }
+
+    /// Iterate over all events affecting a region starting at the given
+    /// pointer.
+    ///
+    /// This gives a view of the lifecycle of a single allocation, including
+    /// any reallocations which moved it to or from the given pointer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Realloc, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x10, 1),
+    ///     Region::new(0x40.into(), 0x20, 1),
+    /// )));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x40.into(), 0x20, 1))));
+    ///
+    /// assert_eq!(2, events.iter_by_address(0x10.into()).count());
+    /// assert_eq!(1, events.iter_by_address(0x20.into()).count());
+    /// assert_eq!(2, events.iter_by_address(0x40.into()).count());
+    /// ```
+    pub fn iter_by_address(&self, ptr: Pointer) -> impl Iterator<Item = &Event> {
+        self.data
+            .iter()
+            .filter(move |e| e.regions().any(|r| r.ptr == ptr))
+    }
+
+    /// Iterate over all events, together with their index, affecting a region
+    /// which overlaps with the given `region`.
+    ///
+    /// A reallocation is included if either the freed or the allocated region
+    /// overlaps. Like in a [Machine], zero-sized regions are considered to
+    /// occupy a single byte.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// let indexes = events
+    ///     .iter_events_for(Region::new(0x18.into(), 0x4, 1))
+    ///     .map(|(index, _)| index)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(vec![0, 2], indexes);
+    /// ```
+    pub fn iter_events_for(&self, region: Region) -> impl Iterator<Item = (usize, &Event)> {
+        let end = |r: Region| r.ptr.saturating_add(usize::max(r.size, 1));
+        let overlaps = move |r: Region| r.ptr < end(region) && region.ptr < end(r);
+
+        self.data
+            .iter()
+            .enumerate()
+            .filter(move |(_, e)| e.regions().any(overlaps))
+    }
+
+    /// Find the deallocation corresponding to the given allocation event.
+    ///
+    /// This is the first [Free][Event::Free] of the allocated pointer which
+    /// follows the allocation. The allocation must be a reference to an
+    /// [Alloc][Event::Alloc] or [AllocZeroed][Event::AllocZeroed] event in
+    /// this collection, otherwise `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    /// events.push(Free(request));
+    ///
+    /// let free = events.find_matching_free(&events[0]).unwrap();
+    /// assert!(free.is_free_with(|r| r.ptr == 0x10.into()));
+    /// assert!(events.find_matching_free(&events[1]).is_none());
+    /// ```
+    pub fn find_matching_free(&self, alloc: &Event) -> Option<&Event> {
+        let index = self.data.iter().position(|e| std::ptr::eq(e, alloc))?;
+        let free = self.matching_free(index)?;
+        Some(&self.data[free])
+    }
+
+    /// Find the index of the deallocation corresponding to the allocation at
+    /// the given index.
+    fn matching_free(&self, index: usize) -> Option<usize> {
+        let ptr = match &self.data[index] {
+            Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                request.region.ptr
+            }
+            _ => return None,
+        };
+
+        let offset = self.data[index + 1..]
+            .iter()
+            .position(|e| e.is_free_with(|r| r.ptr == ptr))?;
+
+        Some(index + 1 + offset)
+    }
+
+    /// Validate the current state and populate the errors collection with any
+    /// violations found.
+    ///
+    /// See [Machine::push] for more details on the kind of validation errors
+    /// that can be raised. If a [region guard][Events::set_region_guard] is
+    /// set, any allocation outside of it is reported as a
+    /// [Violation::OutOfRegion].
+    pub fn validate(&self, errors: &mut Vec<Violation>) {
+        let mut machine = Machine::default();
+        self.validate_with_custom_machine(&mut machine, errors);
+
+        for alloc in machine.trailing_regions() {
+            errors.push(Violation::Leaked { alloc });
+        }
+    }
+
+    /// Validate the events by replaying them into the given `machine`,
+    /// populating the errors collection with any violations found.
+    ///
+    /// Unlike [Events::validate] regions which are still allocated at the end
+    /// are not reported as leaked, since the same machine might be used to
+    /// validate further events. Once all events have been validated leaks
+    /// can be found through [Machine::trailing_regions].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Machine, Region, Request};
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    ///
+    /// let mut first = Events::new();
+    /// first.push(Alloc(request.clone()));
+    ///
+    /// let mut second = Events::new();
+    /// second.push(Free(request));
+    ///
+    /// let mut machine = Machine::default();
+    /// let mut errors = Vec::new();
+    ///
+    /// first.validate_with_custom_machine(&mut machine, &mut errors);
+    /// assert_eq!(1, machine.trailing_regions().len());
+    ///
+    /// second.validate_with_custom_machine(&mut machine, &mut errors);
+    /// assert!(machine.trailing_regions().is_empty());
+    ///
+    /// assert!(errors.is_empty());
+    /// ```
+    pub fn validate_with_custom_machine(&self, machine: &mut Machine, errors: &mut Vec<Violation>) {
+        self.replay(machine, self.as_slice(), |_, _, e| {
+            errors.extend(e);
+            true
+        });
+    }
+
+    /// Validate only the events in the given `range` by replaying them into
+    /// the given `machine`.
+    ///
+    /// This is useful to check a sub-sequence of a large history, where the
+    /// machine has already had the events preceding it applied. See
+    /// [Events::validate_with_custom_machine] for more documentation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Machine, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// let mut machine = Machine::default();
+    /// let mut errors = Vec::new();
+    ///
+    /// events.validate_subset(0..1, &mut machine, &mut errors);
+    /// assert!(errors.is_empty());
+    ///
+    /// events.validate_subset(1..3, &mut machine, &mut errors);
+    /// assert_eq!(1, errors.len());
+    /// assert!(matches!(errors[0], Violation::MissingFree { .. }));
+    /// ```
+    pub fn validate_subset(
+        &self,
+        range: ops::Range<usize>,
+        machine: &mut Machine,
+        errors: &mut Vec<Violation>,
+    ) {
+        self.replay(machine, &self.data[range], |_, _, e| {
+            errors.extend(e);
+            true
+        });
+    }
+
+    /// Validate the events and summarize all leaked regions, together with
+    /// the backtrace of the allocation which leaked if one was captured.
+    ///
+    /// This only reports [Violation::Leaked], any other violations are
+    /// ignored. See [Events::validate] for more documentation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// let leaks = events.summarize_leaks();
+    /// assert_eq!(1, leaks.len());
+    /// assert_eq!(Region::new(0x20.into(), 0x10, 1), leaks[0].0);
+    /// assert!(leaks[0].1.is_none());
+    /// ```
+    pub fn summarize_leaks(&self) -> Vec<(Region, Option<crate::bt::Backtrace>)> {
+        let mut errors = Vec::new();
+        self.validate(&mut errors);
+
+        errors
+            .into_iter()
+            .filter_map(|e| match e {
+                Violation::Leaked { alloc } => Some((alloc.region, alloc.backtrace)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Validate the events like [Events::validate], but don't report
+    /// [Violation::Leaked] for regions whose pointer is in `ignored`.
+    ///
+    /// This is useful for known and expected leaks, like lazily initialized
+    /// statics whose pointers are known in advance.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use checkers::{Event::*, Events, Pointer, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    ///
+    /// let mut ignored = HashSet::new();
+    /// ignored.insert(Pointer::from(0x10));
+    ///
+    /// let mut errors = Vec::new();
+    /// events.validate_with_ignored_ptrs(&ignored, &mut errors);
+    ///
+    /// assert_eq!(1, errors.len());
+    /// assert!(errors[0].is_leaked_with(|r| r.ptr == 0x20.into()));
+    /// ```
+    pub fn validate_with_ignored_ptrs(
+        &self,
+        ignored: &HashSet<Pointer>,
+        errors: &mut Vec<Violation>,
+    ) {
+        let mut machine = Machine::default();
+        self.validate_with_custom_machine(&mut machine, errors);
+
+        for alloc in machine.trailing_regions() {
+            if !ignored.contains(&alloc.region.ptr) {
+                errors.push(Violation::Leaked { alloc });
+            }
+        }
+    }
+
+    /// Validate the events, but only report regions which are leaked.
+    ///
+    /// Any other violations raised while replaying the events in a [Machine]
+    /// are ignored. This is useful for code which is known to perform some
+    /// invalid deallocations, but which must not leak.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// // Never allocated.
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    ///
+    /// let mut errors = Vec::new();
+    /// events.validate_leaks_only(&mut errors);
+    ///
+    /// assert_eq!(1, errors.len());
+    /// assert!(errors[0].is_leaked_with(|r| r.ptr == 0x20.into()));
+    /// ```
+    pub fn validate_leaks_only(&self, errors: &mut Vec<Violation>) {
+        let mut machine = Machine::default();
+        self.replay(&mut machine, self.as_slice(), |_, _, _| true);
+
+        for alloc in machine.trailing_regions() {
+            errors.push(Violation::Leaked { alloc });
+        }
+    }
+
+    /// Check that every deallocation happens after the allocation it matches.
+    ///
+    /// A recorded history always upholds this, but histories which are
+    /// constructed by hand might not. Any [Free][Event::Free] of a pointer
+    /// which isn't live is matched against the next allocation of the same
+    /// pointer, and if one exists a [Violation::OutOfOrderFree] is added to
+    /// `errors`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Free(request.clone()));
+    /// events.push(Alloc(request));
+    ///
+    /// let mut errors = Vec::new();
+    /// events.validate_timeline(&mut errors);
+    ///
+    /// assert_eq!(1, errors.len());
+    /// assert!(matches!(
+    ///     errors[0],
+    ///     Violation::OutOfOrderFree { alloc_index: 1, free_index: 0, .. }
+    /// ));
+    /// ```
+    pub fn validate_timeline(&self, errors: &mut Vec<Violation>) {
+        let mut live = HashSet::<Pointer>::new();
+        // Frees which haven't been matched by a prior allocation, by pointer.
+        let mut pending = BTreeMap::<Pointer, (usize, &Request)>::new();
+
+        for (index, event) in self.data.iter().enumerate() {
+            let ptr = match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                    request.region.ptr
+                }
+                Event::Free(request) => {
+                    let ptr = request.region.ptr;
+
+                    if !live.remove(&ptr) {
+                        pending.entry(ptr).or_insert((index, request));
+                    }
+
+                    continue;
+                }
+                Event::Realloc(realloc) => {
+                    live.remove(&realloc.free.ptr);
+                    realloc.alloc.ptr
+                }
+                _ => continue,
+            };
+
+            match pending.remove(&ptr) {
+                Some((free_index, request)) => {
+                    errors.push(Violation::OutOfOrderFree {
+                        alloc_index: index,
+                        free_index,
+                        request: request.clone(),
+                    });
+                }
+                None => {
+                    live.insert(ptr);
+                }
+            }
+        }
+    }
+
+    /// Validate the current state like [Events::validate], but check
+    /// independent parts of the history in parallel.
+    ///
+    /// The history can't be split by event index, since the state of the
+    /// [Machine] at any point depends on everything that came before it.
+    /// Instead events are partitioned by the address ranges they affect, where
+    /// any two events whose regions overlap end up in the same partition.
+    /// Since events in different partitions never affect the same memory,
+    /// each partition can be replayed in its own [Machine].
+    ///
+    /// The violations produced are the same, and in the same order, as with
+    /// [Events::validate]. This is only available with the `parallel` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x100.into(), 0x10, 1))));
+    /// events.push(Free(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// let mut errors = Vec::new();
+    /// events.validate_parallel(&mut errors);
+    ///
+    /// assert_eq!(2, errors.len());
+    /// assert!(matches!(errors[0], Violation::MissingFree { .. }));
+    /// assert!(matches!(errors[1], Violation::Leaked { .. }));
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn validate_parallel(&self, errors: &mut Vec<Violation>) {
+        use rayon::prelude::*;
+
+        let results = self
+            .partition_by_address()
+            .into_par_iter()
+            .map(|indexes| {
+                let mut machine = Machine::default();
+                let mut errors = Vec::new();
+
+                for index in indexes {
+                    if let Some(e) = self.push_guarded(&mut machine, &self.data[index]) {
+                        errors.push((index, e));
+                    }
+                }
+
+                (errors, machine.trailing_regions())
+            })
+            .collect::<Vec<_>>();
+
+        let mut violations = Vec::new();
+        let mut leaked = Vec::new();
+
+        for (e, l) in results {
+            violations.extend(e);
+            leaked.extend(l);
+        }
+
+        violations.sort_by_key(|(index, _)| *index);
+        leaked.sort_by_key(|alloc| alloc.region.ptr);
+
+        errors.extend(violations.into_iter().map(|(_, e)| e));
+        errors.extend(leaked.into_iter().map(|alloc| Violation::Leaked { alloc }));
+    }
+
+    /// Partition the indexes of all events, so that events affecting
+    /// overlapping regions end up in the same partition. The indexes in each
+    /// partition are in order.
+    #[cfg(feature = "parallel")]
+    fn partition_by_address(&self) -> Vec<Vec<usize>> {
+        fn find(parents: &mut [usize], mut n: usize) -> usize {
+            while parents[n] != n {
+                parents[n] = parents[parents[n]];
+                n = parents[n];
+            }
+
+            n
+        }
+
+        // Note: zero-sized regions are considered to occupy one byte, like in
+        // a machine.
+        let mut ranges = self
+            .data
+            .iter()
+            .enumerate()
+            .flat_map(|(index, e)| {
+                e.regions()
+                    .map(move |r| (r.ptr, r.ptr.saturating_add(usize::max(r.size, 1)), index))
+            })
+            .collect::<Vec<_>>();
+
+        ranges.sort_unstable();
+
+        let mut parents = (0..self.data.len()).collect::<Vec<_>>();
+        let mut current = None::<(Pointer, usize)>;
+
+        for (start, end, index) in ranges {
+            current = match current {
+                Some((current_end, root)) if start < current_end => {
+                    let a = find(&mut parents, root);
+                    let b = find(&mut parents, index);
+                    parents[b] = a;
+                    Some((Pointer::max(current_end, end), a))
+                }
+                _ => Some((end, index)),
+            };
+        }
+
+        let mut partitions = BTreeMap::<usize, Vec<usize>>::new();
+
+        for index in 0..self.data.len() {
+            let root = find(&mut parents, index);
+            partitions.entry(root).or_default().push(index);
+        }
+
+        partitions.into_values().collect()
+    }
+
+    /// Check that all allocated regions are aligned as requested.
+    ///
+    /// This produces the same [Violation::MisalignedAlloc] violations as
+    /// [Events::validate], but doesn't need to replay the history in a
+    /// [Machine]. Regions allocated by reallocations are checked as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 8))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x24.into(), 0x10, 8))));
+    ///
+    /// let violations = events.check_alignment_invariants();
+    /// assert_eq!(1, violations.len());
+    /// assert!(matches!(violations[0], Violation::MisalignedAlloc { .. }));
+    /// ```
+    pub fn check_alignment_invariants(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        let misaligned = |region: Region| !region.ptr.is_aligned_with(region.align);
+
+        for event in self.as_slice() {
+            let alloc = match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. })
+                    if misaligned(request.region) =>
+                {
+                    request.clone()
+                }
+                Event::Realloc(realloc) if misaligned(realloc.alloc) => realloc.alloc(),
+                _ => continue,
+            };
+
+            violations.push(Violation::MisalignedAlloc { alloc });
+        }
+
+        violations
+    }
+
+    /// Run a set of cheap checks over the events themselves, without
+    /// replaying them in a [Machine].
+    ///
+    /// This finds zero-sized allocations and deallocations, regions with an
+    /// alignment of zero or which is not a power of two, and regions whose
+    /// size overflows `isize`. None of these are necessarily errors, but they
+    /// can catch obviously corrupt events before running [Events::validate].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, SanityWarning};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x30.into(), 0x10, 3))));
+    ///
+    /// let warnings = events.sanity_check();
+    /// assert_eq!(2, warnings.len());
+    /// assert!(matches!(warnings[0], SanityWarning::ZeroSizeAlloc { index: 1, .. }));
+    /// assert!(matches!(warnings[1], SanityWarning::NonPowerOfTwoAlignment { index: 2, .. }));
+    /// ```
+    pub fn sanity_check(&self) -> Vec<SanityWarning> {
+        let mut warnings = Vec::new();
+
+        for (index, event) in self.data.iter().enumerate() {
+            match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. })
+                    if request.region.size == 0 =>
+                {
+                    warnings.push(SanityWarning::ZeroSizeAlloc {
+                        index,
+                        region: request.region,
+                    });
+                }
+                Event::Free(request) if request.region.size == 0 => {
+                    warnings.push(SanityWarning::ZeroSizeFree {
+                        index,
+                        region: request.region,
+                    });
+                }
+                _ => (),
+            }
+
+            for region in event.regions() {
+                if region.align == 0 {
+                    warnings.push(SanityWarning::ZeroAlignment { index, region });
+                } else if !region.align.is_power_of_two() {
+                    warnings.push(SanityWarning::NonPowerOfTwoAlignment { index, region });
+                }
+
+                if region.size > isize::MAX as usize {
+                    warnings.push(SanityWarning::SizeOverflow { index, region });
+                }
+            }
+        }
+
+        warnings
+    }
+
+    /// Check that no zero-sized regions are allocated.
+    ///
+    /// Zero-sized allocations are permitted by the allocator, so this isn't
+    /// checked by [Events::validate]. But they often indicate bugs, since
+    /// types like `Vec` never allocate when they're empty. Produces a
+    /// [Violation::ZeroSizeAlloc] for each [Alloc][Event::Alloc] and
+    /// [AllocZeroed][Event::AllocZeroed] event of size zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0, 1))));
+    ///
+    /// let violations = events.check_no_zero_size_allocs();
+    /// assert_eq!(1, violations.len());
+    /// assert!(matches!(violations[0], Violation::ZeroSizeAlloc { .. }));
+    /// ```
+    pub fn check_no_zero_size_allocs(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for event in self.as_slice() {
+            match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. })
+                    if request.region.size == 0 =>
+                {
+                    violations.push(Violation::ZeroSizeAlloc {
+                        alloc: request.clone(),
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        violations
+    }
+
+    /// Check that no zero-sized regions are freed.
+    ///
+    /// This is the deallocation counterpart to
+    /// [Events::check_no_zero_size_allocs], producing a
+    /// [Violation::ZeroSizeFree] for each [Free][Event::Free] event of size
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x20.into(), 0, 1))));
+    ///
+    /// let violations = events.check_no_size_zero_frees();
+    /// assert_eq!(1, violations.len());
+    /// assert!(matches!(violations[0], Violation::ZeroSizeFree { .. }));
+    /// ```
+    pub fn check_no_size_zero_frees(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for event in self.as_slice() {
+            match event {
+                Event::Free(request) if request.region.size == 0 => {
+                    violations.push(Violation::ZeroSizeFree {
+                        request: request.clone(),
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        violations
+    }
+
+    /// Check that no regions are allocated with an alignment larger than
+    /// `max_align`.
+    ///
+    /// System allocators often don't support alignments larger than the page
+    /// size of the operating system, which is typically 4096 bytes. Produces a
+    /// [Violation::OveralignedAlloc] for each [Alloc][Event::Alloc] and
+    /// [AllocZeroed][Event::AllocZeroed] event which exceeds it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x1000.into(), 0x10, 8))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x4000.into(), 0x10, 0x4000))));
+    ///
+    /// let violations = events.check_no_overaligned_allocs(4096);
+    /// assert_eq!(1, violations.len());
+    /// assert!(matches!(violations[0], Violation::OveralignedAlloc { max_align: 4096, .. }));
+    /// ```
+    pub fn check_no_overaligned_allocs(&self, max_align: usize) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for event in self.as_slice() {
+            match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. })
+                    if request.region.align > max_align =>
+                {
+                    violations.push(Violation::OveralignedAlloc {
+                        alloc: request.clone(),
+                        max_align,
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        violations
+    }
+
+    /// Check that no allocated region overlaps with another live region.
+    ///
+    /// This produces the same [Violation::ConflictingAlloc] violations as
+    /// [Events::validate]. Since addresses are reused once freed, it's not
+    /// enough to compare regions by address alone. Instead only the bounds of
+    /// the live regions are tracked while the history is replayed, skipping
+    /// the remaining bookkeeping performed by a [Machine].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request.clone()));
+    /// // Note: reusing a freed region is fine.
+    /// events.push(Alloc(request));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x18.into(), 0x10, 1))));
+    ///
+    /// let mut errors = Vec::new();
+    /// events.validate_no_overlapping_allocs(&mut errors);
+    ///
+    /// assert_eq!(1, errors.len());
+    /// assert!(matches!(errors[0], Violation::ConflictingAlloc { .. }));
+    /// ```
+    pub fn validate_no_overlapping_allocs(&self, errors: &mut Vec<Violation>) {
+        // Live regions by pointer, with the index of the allocating event.
+        let mut live = BTreeMap::<Pointer, (Region, usize)>::new();
+
+        for (index, event) in self.data.iter().enumerate() {
+            let region = match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                    request.region
+                }
+                Event::Free(request) => {
+                    live.remove(&request.region.ptr);
+                    continue;
+                }
+                Event::Realloc(realloc) => {
+                    live.remove(&realloc.free.ptr);
+                    realloc.alloc
+                }
+                _ => continue,
+            };
+
+            let start = region.ptr;
+            let end = start.saturating_add(usize::max(region.size, 1));
+
+            let head = live
+                .range(..start)
+                .next_back()
+                .filter(|(_, (r, _))| start < r.ptr.saturating_add(r.size));
+
+            let existing = head.or_else(|| live.range(start..end).next());
+
+            if let Some((_, &(_, existing))) = existing {
+                if let (Some(request), Some(existing)) = (
+                    allocated_request(event),
+                    allocated_request(&self.data[existing]),
+                ) {
+                    errors.push(Violation::ConflictingAlloc { request, existing });
+                }
+
+                continue;
+            }
+
+            live.insert(start, (region, index));
+        }
+    }
+
+    /// Replay `events` into `machine`, calling `visit` after each event with
+    /// the state of the machine and the violation raised by the event, if any.
+    ///
+    /// Replaying stops early if `visit` returns `false`.
+    fn replay<'a, I, F>(&self, machine: &mut Machine, events: I, mut visit: F)
+    where
+        I: IntoIterator<Item = &'a Event>,
+        F: FnMut(&Event, &Machine, Option<Violation>) -> bool,
+    {
+        for event in events {
+            let violation = self.push_guarded(machine, event);
+
+            if !visit(event, machine, violation) {
+                break;
+            }
+        }
+    }
+
+    /// Push a single event into `machine`, and if it succeeds check the
+    /// region it allocates against the [region guard][Events::region_guard].
+    fn push_guarded(&self, machine: &mut Machine, event: &Event) -> Option<Violation> {
+        if let Err(e) = machine.push(event) {
+            return Some(e);
+        }
+
+        let (lo, hi) = self.guard?;
+        let alloc = allocated_request(event)?;
+        let region = alloc.region;
+
+        if region.ptr < lo || hi < region.ptr.saturating_add(region.size) {
+            return Some(Violation::OutOfRegion { alloc, lo, hi });
+        }
+
+        None
+    }
+
+    /// Validate the current state and print any violations found to stderr.
+    ///
+    /// Returns `true` if no violations were found. This runs while the
+    /// allocator is muted, see [is_muted][crate::is_muted].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// assert!(!events.validate_and_print());
+    ///
+    /// events.push(Free(request));
+    /// assert!(events.validate_and_print());
+    /// ```
+    pub fn validate_and_print(&self) -> bool {
+        crate::with_muted(|| {
+            let mut errors = Vec::new();
+            self.validate(&mut errors);
+
+            for e in &errors {
+                eprintln!("{}", e);
+            }
+
+            errors.is_empty()
+        })
+    }
+
+    /// Get the first violation produced when validating the history, if any.
+    ///
+    /// This is the lazy counterpart to [Events::validate], and avoids
+    /// replaying the rest of the history when all you care about is whether
+    /// there are any violations at all. Violations produced by the [Machine]
+    /// are returned before any leaked regions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// assert!(matches!(events.first_violation(), Some(Violation::Leaked { .. })));
+    ///
+    /// events.push(Free(request.clone()));
+    /// assert!(events.first_violation().is_none());
+    ///
+    /// events.push(Free(request));
+    /// assert!(matches!(events.first_violation(), Some(Violation::MissingFree { .. })));
+    /// ```
+    pub fn first_violation(&self) -> Option<Violation> {
+        self.try_validate().err()
+    }
+
+    /// Replay the history, returning the first violation found.
+    fn try_validate(&self) -> Result<(), Violation> {
+        let mut machine = Machine::default();
+        let mut violation = None;
+
+        self.replay(&mut machine, self.as_slice(), |_, _, e| {
+            violation = e;
+            violation.is_none()
+        });
+
+        if let Some(e) = violation {
+            return Err(e);
+        }
+
+        if let Some(alloc) = machine.trailing_regions().into_iter().next() {
+            return Err(Violation::Leaked { alloc });
+        }
+
+        Ok(())
+    }
+
+    /// Consume the collection and replay it in a [Machine], returning the
+    /// final state of the machine.
+    ///
+    /// If a violation is encountered, the replay stops and the machine is
+    /// returned together with the violation so that the state at the point of
+    /// the error can be inspected. Leaked regions are not considered
+    /// violations, they can be found through [Machine::trailing_regions].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    ///
+    /// let machine = events.clone().into_machine().unwrap();
+    /// assert_eq!(1, machine.trailing_regions().len());
+    ///
+    /// events.push(Free(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// let (machine, violation) = events.into_machine().unwrap_err();
+    /// assert!(machine.trailing_regions().is_empty());
+    /// assert!(matches!(violation, Violation::MissingFree { .. }));
+    /// ```
+    // Note: this is a cold error path, so there's no need to box it.
+    #[allow(clippy::result_large_err)]
+    pub fn into_machine(self) -> Result<Machine, (Machine, Violation)> {
+        let mut machine = Machine::default();
+        let mut violation = None;
+
+        self.replay(&mut machine, self.as_slice(), |_, _, e| {
+            violation = e;
+            violation.is_none()
+        });
+
+        match violation {
+            Some(e) => Err((machine, e)),
+            None => Ok(machine),
+        }
+    }
+
+    /// Validate the current state like [Events::validate], but also check
+    /// each event against the given [AllocationPolicy].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, MaxSizePolicy, Region, Request};
+    ///
+    /// let mut events = Events::new();
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// let mut errors = Vec::new();
+    /// events.validate_with_policy(&mut errors, &MaxSizePolicy(0x08));
+    ///
+    /// // One oversized allocation, and one leaked region.
+    /// assert_eq!(2, errors.len());
+    /// ```
+    pub fn validate_with_policy(&self, errors: &mut Vec<Violation>, policy: &dyn AllocationPolicy) {
+        let mut machine = Machine::default();
+
+        self.replay(&mut machine, self.as_slice(), |event, machine, e| {
+            errors.extend(e);
+            errors.extend(policy.check(event, machine));
+            true
+        });
+
+        for alloc in machine.trailing_regions() {
+            errors.push(Violation::Leaked { alloc });
+        }
+    }
+
+    /// Find all deallocations which are likely double-frees.
+    ///
+    /// These are the [Free][Event::Free] events which are reported as
+    /// [Violation::MissingFree] during validation, and whose address has
+    /// previously been freed without being allocated again since. Other
+    /// missing frees are likely deallocations of wild pointers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// // Never allocated.
+    /// let request = Request::without_backtrace(Region::new(0x40.into(), 0x10, 1));
+    /// events.push(Free(request));
+    ///
+    /// let candidates = events.detect_double_free_candidates();
+    /// assert_eq!(1, candidates.len());
+    /// assert!(candidates[0].is_free_with(|r| r.ptr == 0x10.into()));
+    /// ```
+    pub fn detect_double_free_candidates(&self) -> Vec<&Event> {
+        let mut machine = Machine::default();
+        let mut freed = HashSet::<Pointer>::new();
+        let mut candidates = Vec::new();
+
+        for event in self.as_slice() {
+            let result = machine.push(event);
+
+            match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                    freed.remove(&request.region.ptr);
+                }
+                Event::Free(request) => match result {
+                    Ok(()) => {
+                        freed.insert(request.region.ptr);
+                    }
+                    Err(Violation::MissingFree { .. }) => {
+                        if freed.contains(&request.region.ptr) {
+                            candidates.push(event);
+                        }
+                    }
+                    Err(..) => (),
+                },
+                Event::Realloc(realloc) => {
+                    freed.insert(realloc.free.ptr);
+                    freed.remove(&realloc.alloc.ptr);
+                }
+                _ => (),
+            }
+        }
+
+        candidates
+    }
+
+    /// Detect addresses which might be subject to a use-after-free.
+    ///
+    /// Without tracking pointer accesses a use-after-free can't be detected
+    /// reliably, so this is a best-effort heuristic. An address is reported if
+    /// it was freed, allocated again, and then freed twice without another
+    /// allocation in between. This is what happens if a stale pointer to a
+    /// recycled region is deallocated by its previous owner.
+    ///
+    /// Each address is reported at most once, in the order in which the
+    /// pattern was detected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Pointer, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request.clone()));
+    /// // The region is recycled by a new owner.
+    /// events.push(Alloc(request.clone()));
+    /// // The stale owner frees it again, followed by the new owner.
+    /// events.push(Free(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// // Never recycled.
+    /// let request = Request::without_backtrace(Region::new(0x40.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// assert_eq!(vec![Pointer::from(0x10)], events.detect_use_after_free_candidates());
+    /// ```
+    pub fn detect_use_after_free_candidates(&self) -> Vec<Pointer> {
+        // Number of frees of each address since it was last allocated.
+        let mut frees = HashMap::<Pointer, usize>::new();
+        let mut recycled = HashSet::<Pointer>::new();
+        let mut reported = HashSet::<Pointer>::new();
+        let mut candidates = Vec::new();
+
+        for event in self.as_slice() {
+            let (free, alloc) = match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                    (None, Some(request.region.ptr))
+                }
+                Event::Free(request) => (Some(request.region.ptr), None),
+                Event::Realloc(realloc) => (Some(realloc.free.ptr), Some(realloc.alloc.ptr)),
+                _ => continue,
+            };
+
+            if let Some(ptr) = free {
+                let count = frees.entry(ptr).or_default();
+                *count += 1;
+
+                if *count == 2 && recycled.contains(&ptr) && reported.insert(ptr) {
+                    candidates.push(ptr);
+                }
+            }
+
+            if let Some(ptr) = alloc {
+                if frees.insert(ptr, 0).unwrap_or_default() > 0 {
+                    recycled.insert(ptr);
+                } else {
+                    recycled.remove(&ptr);
+                }
+            }
+        }
+
+        candidates
+    }
+
+    /// Assert that the events consist of exactly one allocation followed by
+    /// exactly one deallocation of the same pointer, like `[Alloc(_), ...,
+    /// Free(_)]`, with no reallocations.
+    ///
+    /// This is the expected pattern for types like `Box<T>` which allocate
+    /// once when constructed and deallocate once when dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the events don't match the pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// events.assert_single_allocation_pattern();
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    ///
+    /// events.assert_single_allocation_pattern();
+    /// ```
+    pub fn assert_single_allocation_pattern(&self) {
+        crate::with_muted(|| {
+            let (allocs, frees, reallocs) = (self.allocs(), self.frees(), self.reallocs());
+
+            if allocs != 1 || frees != 1 || reallocs != 0 {
+                panic!(
+                    "expected a single allocation and deallocation, but got {} allocations, {} deallocations and {} reallocations",
+                    allocs, frees, reallocs
+                );
+            }
+
+            let alloc = match self.data.first() {
+                Some(Event::Alloc(request))
+                | Some(Event::AllocZeroed(AllocZeroed { request, .. })) => request,
+                first => panic!(
+                    "expected the first event to be an allocation, but got {:?}",
+                    first
+                ),
+            };
+
+            let free = match self.data.last() {
+                Some(Event::Free(request)) => request,
+                last => panic!(
+                    "expected the last event to be a deallocation, but got {:?}",
+                    last
+                ),
+            };
+
+            if alloc.region.ptr != free.region.ptr {
+                panic!(
+                    "expected the deallocation ({}) to free the allocated region ({})",
+                    free.region, alloc.region
+                );
+            }
+        });
+    }
+
+    /// Assert that the first event is an allocation, either an
+    /// [Alloc][Event::Alloc] or an [AllocZeroed][Event::AllocZeroed].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the first event is not an allocation, or if there are no
+    /// events.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// events.assert_first_event_is_alloc();
+    /// events.assert_last_event_is_free();
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// events.assert_first_event_is_alloc();
+    /// ```
+    pub fn assert_first_event_is_alloc(&self) {
+        crate::with_muted(|| match self.data.first() {
+            Some(Event::Alloc(..)) | Some(Event::AllocZeroed(..)) => (),
+            first => panic!(
+                "expected the first event to be an allocation, but got {:?}",
+                first
+            ),
+        });
+    }
+
+    /// Assert that the last event is a [Free][Event::Free].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the last event is not a deallocation, or if there are no
+    /// events.
+    ///
+    /// See [Events::assert_first_event_is_alloc] for more documentation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// events.assert_last_event_is_free();
+    /// ```
+    pub fn assert_last_event_is_free(&self) {
+        crate::with_muted(|| match self.data.last() {
+            Some(Event::Free(..)) => (),
+            last => panic!(
+                "expected the last event to be a deallocation, but got {:?}",
+                last
+            ),
+        });
+    }
+
+    /// Assert that the number of allocations matches the number of
+    /// deallocations.
+    ///
+    /// Reallocations count as both an allocation and a deallocation. Unlike
+    /// [Events::validate] this doesn't check that deallocations match the
+    /// regions which were allocated, it's only a quick sanity check.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counts differ, listing the allocations and deallocations
+    /// which couldn't be matched by pointer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// events.assert_balanced();
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// events.assert_balanced();
+    /// ```
+    pub fn assert_balanced(&self) {
+        crate::with_muted(|| {
+            let (allocs, frees) = (self.allocs(), self.frees());
+
+            if allocs == frees {
+                return;
+            }
+
+            // Live allocations by pointer, with the index of the allocating
+            // event.
+            let mut live = BTreeMap::<Pointer, usize>::new();
+            let mut unmatched = Vec::new();
+
+            for (index, event) in self.data.iter().enumerate() {
+                match event {
+                    Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                        live.insert(request.region.ptr, index);
+                    }
+                    Event::Free(request) => match live.remove(&request.region.ptr) {
+                        Some(..) => (),
+                        None => unmatched.push(index),
+                    },
+                    Event::Realloc(realloc) => {
+                        if let Some(alloc) = live.remove(&realloc.free.ptr) {
+                            live.insert(realloc.alloc.ptr, alloc);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+
+            unmatched.extend(live.values().copied());
+            unmatched.sort_unstable();
+
+            let mut message = format!(
+                "expected allocations and deallocations to be balanced, but got {} allocations and {} deallocations",
+                allocs, frees
+            );
+
+            for index in unmatched {
+                message.push_str(&format!("\n  {}: {:?}", index, self.data[index]));
+            }
+
+            panic!("{}", message);
+        });
+    }
+
+    /// Assert that the number of bytes allocated matches the number of bytes
+    /// deallocated.
+    ///
+    /// Reallocations count the size of the region they allocate as allocated,
+    /// and the size of the region they free as deallocated. This is stronger
+    /// than [Events::assert_balanced], since it catches large allocations
+    /// being matched by small deallocations, but unlike [Events::validate] it
+    /// doesn't check that deallocations match the regions which were
+    /// allocated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of bytes allocated and deallocated differ.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Realloc, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x10, 1),
+    ///     Region::new(0x20.into(), 0x20, 1),
+    /// )));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x20.into(), 0x20, 1))));
+    ///
+    /// events.assert_net_zero_allocations();
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x20, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// events.assert_net_zero_allocations();
+    /// ```
+    pub fn assert_net_zero_allocations(&self) {
+        crate::with_muted(|| {
+            let mut allocated = 0usize;
+            let mut freed = 0usize;
+
+            for event in self.as_slice() {
+                match event {
+                    Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                        allocated = allocated.saturating_add(request.region.size);
+                    }
+                    Event::Free(request) => {
+                        freed = freed.saturating_add(request.region.size);
+                    }
+                    Event::Realloc(realloc) => {
+                        allocated = allocated.saturating_add(realloc.alloc.size);
+                        freed = freed.saturating_add(realloc.free.size);
+                    }
+                    _ => (),
+                }
+            }
+
+            if allocated != freed {
+                panic!(
+                    "expected the net number of bytes allocated to be zero, but got {} bytes allocated and {} bytes deallocated",
+                    allocated, freed
+                );
+            }
+        });
+    }
+
+    /// Assert that every deallocation matches an allocation recorded earlier
+    /// in the same events.
+    ///
+    /// In a single-threaded test, a deallocation of memory which wasn't
+    /// allocated in the same session usually means that the memory was
+    /// allocated by another thread, or by another test running in the same
+    /// process. Reallocations must similarly reallocate a region which was
+    /// previously allocated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any deallocation or reallocation has no matching allocation,
+    /// listing each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// events.assert_no_cross_thread_frees();
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// events.assert_no_cross_thread_frees();
+    /// ```
+    pub fn assert_no_cross_thread_frees(&self) {
+        crate::with_muted(|| {
+            let mut live = HashSet::<Pointer>::new();
+            let mut unmatched = Vec::new();
+
+            for (index, event) in self.data.iter().enumerate() {
+                match event {
+                    Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                        live.insert(request.region.ptr);
+                    }
+                    Event::Free(request) => match live.remove(&request.region.ptr) {
+                        true => (),
+                        false => unmatched.push(index),
+                    },
+                    Event::Realloc(realloc) => {
+                        if !live.remove(&realloc.free.ptr) {
+                            unmatched.push(index);
+                        }
+
+                        live.insert(realloc.alloc.ptr);
+                    }
+                    _ => (),
+                }
+            }
+
+            if unmatched.is_empty() {
+                return;
+            }
+
+            let mut message = format!(
+                "expected every deallocation to match a prior allocation, but got {} which didn't",
+                unmatched.len()
+            );
+
+            for index in unmatched {
+                message.push_str(&format!("\n  {}: {:?}", index, self.data[index]));
+            }
+
+            message.push_str(
+                "\nhint: the memory might have been allocated by another thread or test, try putting each test in its own file under `tests/`",
+            );
+
+            panic!("{}", message);
+        });
+    }
+
+    /// Assert that there are no reallocations.
+    ///
+    /// This is useful for types which guarantee that their backing memory
+    /// never moves, like pinned or otherwise address-stable collections.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any [Realloc][Event::Realloc] events, listing all of
+    /// them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    ///
+    /// events.assert_no_reallocs();
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Realloc};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x10, 1),
+    ///     Region::new(0x20.into(), 0x20, 1),
+    /// )));
+    ///
+    /// events.assert_no_reallocs();
+    /// ```
+    pub fn assert_no_reallocs(&self) {
+        crate::with_muted(|| {
+            let reallocs = self.reallocs();
+
+            if reallocs == 0 {
+                return;
+            }
+
+            let mut message = format!("expected no reallocations, but got {}", reallocs);
+
+            for (index, event) in self.data.iter().enumerate() {
+                if let Event::Realloc(realloc) = event {
+                    message.push_str(&format!(
+                        "\n  {}: {} -> {}",
+                        index, realloc.free, realloc.alloc
+                    ));
+                }
+            }
+
+            panic!("{}", message);
+        });
+    }
+
+    /// Assert that no reallocation grew a region by more than the given
+    /// factor.
+    ///
+    /// The factor of a reallocation is computed the same way as in
+    /// [Events::max_realloc_factor], so reallocations of zero-sized regions
+    /// are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_factor` is not positive, or if any reallocation exceeds
+    /// it, listing the offending reallocations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Realloc};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x10, 1),
+    ///     Region::new(0x20.into(), 0x20, 1),
+    /// )));
+    ///
+    /// events.assert_realloc_factor_at_most(2.0);
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Realloc};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x10, 1),
+    ///     Region::new(0x20.into(), 0x40, 1),
+    /// )));
+    ///
+    /// events.assert_realloc_factor_at_most(2.0);
+    /// ```
+    pub fn assert_realloc_factor_at_most(&self, max_factor: f64) {
+        crate::with_muted(|| {
+            if max_factor.is_nan() || max_factor <= 0.0 {
+                panic!("expected a positive max factor, but got {}", max_factor);
+            }
+
+            let mut message = String::new();
+            let mut count = 0;
+
+            for (index, event) in self.data.iter().enumerate() {
+                let realloc = match event {
+                    Event::Realloc(realloc) if realloc.free.size > 0 => realloc,
+                    _ => continue,
+                };
+
+                let factor = realloc.alloc.size as f64 / realloc.free.size as f64;
+
+                if factor > max_factor {
+                    count += 1;
+                    message.push_str(&format!(
+                        "\n  {}: {} -> {} (factor {})",
+                        index, realloc.free.size, realloc.alloc.size, factor
+                    ));
+                }
+            }
+
+            if count > 0 {
+                panic!(
+                    "expected reallocations to grow by a factor of at most {}, but got {} exceeding it{}",
+                    max_factor, count, message
+                );
+            }
+        });
+    }
+
+    /// Assert that every reallocation grows the region it reallocates.
+    ///
+    /// This is useful for data structures which only ever grow their backing
+    /// storage, and should never shrink it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any reallocation allocates a region which is not larger than
+    /// the one it frees, listing the offending reallocations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Realloc};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x10, 1),
+    ///     Region::new(0x20.into(), 0x20, 1),
+    /// )));
+    ///
+    /// events.assert_strictly_increasing_alloc_sizes();
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Realloc};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x10, 1),
+    ///     Region::new(0x20.into(), 0x10, 1),
+    /// )));
+    ///
+    /// events.assert_strictly_increasing_alloc_sizes();
+    /// ```
+    pub fn assert_strictly_increasing_alloc_sizes(&self) {
+        crate::with_muted(|| {
+            let mut message = String::new();
+            let mut count = 0;
+
+            for (index, event) in self.data.iter().enumerate() {
+                match event {
+                    Event::Realloc(realloc) if realloc.alloc.size <= realloc.free.size => {
+                        count += 1;
+                        message.push_str(&format!(
+                            "\n  {}: {} -> {}",
+                            index, realloc.free.size, realloc.alloc.size
+                        ));
+                    }
+                    _ => (),
+                }
+            }
+
+            if count > 0 {
+                panic!(
+                    "expected reallocations to only grow, but got {} which didn't{}",
+                    count, message
+                );
+            }
+        });
+    }
+
+    /// Assert that no allocated region overlaps with another live region.
+    ///
+    /// Only the overlap check performed by
+    /// [Events::validate_no_overlapping_allocs] is run, which is useful for
+    /// testing the partitioning logic of custom allocators.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any allocations conflict, listing each conflicting pair.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    ///
+    /// events.assert_no_conflicting_allocs();
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x18.into(), 0x10, 1))));
+    ///
+    /// events.assert_no_conflicting_allocs();
+    /// ```
+    pub fn assert_no_conflicting_allocs(&self) {
+        crate::with_muted(|| {
+            let mut errors = Vec::new();
+            self.validate_no_overlapping_allocs(&mut errors);
+
+            if errors.is_empty() {
+                return;
+            }
+
+            let mut message = format!(
+                "expected no conflicting allocations, but got {}",
+                errors.len()
+            );
+
+            for e in &errors {
+                if let Violation::ConflictingAlloc { request, existing } = e {
+                    message.push_str(&format!(
+                        "\n  {} overlaps {}",
+                        request.region, existing.region
+                    ));
+                }
+            }
+
+            panic!("{}", message);
+        });
+    }
+
+    /// Max amount of memory used according to this event history.
+    ///
+    /// Returns the first violation encountered if the history is not sound.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request));
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x20.into(), 0x10, 1));
+    /// events.push(Alloc(request));
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Free(request));
+    ///
+    /// assert_eq!(0x20, events.max_memory_used().unwrap());
+    /// ```
+    pub fn max_memory_used(&self) -> Result<usize, Violation> {
+        let mut machine = Machine::default();
+
+        let mut max = 0usize;
+
+        for event in self.as_slice() {
+            machine.push(event)?;
+            max = usize::max(machine.memory_used, max);
+        }
+
+        Ok(max)
+    }
+
+    /// The number of bytes which are still allocated after all events.
+    ///
+    /// This sums the size of all allocations, minus the size of all
+    /// deallocations, with reallocations contributing their size delta. Unlike
+    /// [Events::max_memory_used] the history isn't replayed in a [Machine], so
+    /// regions are not matched against each other.
+    ///
+    /// Returns a [Violation::MissingFree] for the first deallocation which
+    /// frees more bytes than are live at that point. Since regions aren't
+    /// matched, a double-free is only caught by this if it happens to do so.
+    /// Use [Events::validate] to reliably detect double-frees.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request, Violation};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    /// events.push(Free(request.clone()));
+    ///
+    /// assert_eq!(0x10, events.total_live_bytes_at_end().unwrap());
+    ///
+    /// // Double-free which doesn't free more bytes than are live.
+    /// events.push(Free(request.clone()));
+    /// assert_eq!(0, events.total_live_bytes_at_end().unwrap());
+    ///
+    /// events.push(Free(request));
+    /// assert!(matches!(
+    ///     events.total_live_bytes_at_end().unwrap_err(),
+    ///     Violation::MissingFree { .. }
+    /// ));
+    /// ```
+    pub fn total_live_bytes_at_end(&self) -> Result<usize, Violation> {
+        let mut total = 0usize;
+
+        for event in self.as_slice() {
+            match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                    total = total.saturating_add(request.region.size);
+                }
+                Event::Free(request) => {
+                    total = match total.checked_sub(request.region.size) {
+                        Some(total) => total,
+                        None => {
+                            return Err(Violation::MissingFree {
+                                request: request.clone(),
+                            })
+                        }
+                    };
+                }
+                Event::Realloc(realloc) => {
+                    total = match total.checked_sub(realloc.free.size) {
+                        Some(total) => total.saturating_add(realloc.alloc.size),
+                        None => {
+                            return Err(Violation::MissingFree {
+                                request: realloc.free(),
+                            })
+                        }
+                    };
+                }
+                _ => (),
+            }
+        }
+
+        Ok(total)
+    }
+
+    /// The total number of bytes ever allocated, up to and including each
+    /// event.
+    ///
+    /// Reallocations count the full size of the region they allocate, while
+    /// deallocations don't affect the total. The slope of this series is the
+    /// allocation rate, which is useful to find bursts of allocations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Realloc, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request.clone()));
+    /// events.push(Free(request));
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x08, 1))));
+    /// events.push(Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x08, 1),
+    ///     Region::new(0x20.into(), 0x20, 1),
+    /// )));
+    ///
+    /// assert_eq!(vec![0x10, 0x10, 0x18, 0x38], events.cumulative_bytes_allocated_at_each_event());
+    /// ```
+    pub fn cumulative_bytes_allocated_at_each_event(&self) -> Vec<usize> {
+        let mut total = 0usize;
+
+        self.data
+            .iter()
+            .map(|e| {
+                let size = match e {
+                    Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                        request.region.size
+                    }
+                    Event::Realloc(realloc) => realloc.alloc.size,
+                    _ => 0,
+                };
+
+                total = total.saturating_add(size);
+                total
+            })
+            .collect()
+    }
+
+    /// Run-length encode the number of live bytes after each event.
+    ///
+    /// Each interval is `(start, end, bytes)`, where `start` and `end` are the
+    /// inclusive indexes of the first and last event during which `bytes`
+    /// were live. Consecutive events which don't change the number of live
+    /// bytes are merged into the same interval.
+    ///
+    /// Events are replayed in a [Machine] while ignoring any violations, see
+    /// [Machine::push] for how they affect the number of live bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    /// events.push(AllocFailed);
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+    /// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+    ///
+    /// assert_eq!(
+    ///     vec![(0, 1, 0x10), (2, 2, 0x20), (3, 3, 0x10), (4, 4, 0)],
+    ///     events.watermark_intervals()
+    /// );
+    /// ```
+    pub fn watermark_intervals(&self) -> Vec<(usize, usize, usize)> {
+        let mut machine = Machine::default();
+        let mut intervals = Vec::<(usize, usize, usize)>::new();
+
+        for (index, event) in self.data.iter().enumerate() {
+            let _ = machine.push(event);
+
+            match intervals.last_mut() {
+                Some((_, end, bytes)) if *bytes == machine.memory_used => {
+                    *end = index;
+                }
+                _ => intervals.push((index, index, machine.memory_used)),
+            }
+        }
+
+        intervals
+    }
+
+    /// Consume the collection of events and produce a human-readable
+    /// [AllocationReport].
+    ///
+    /// Unlike [Events::max_memory_used], the history is replayed in full even
+    /// if it contains violations. Events which produce a violation don't
+    /// affect memory usage.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request));
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x20.into(), 0x10, 1));
+    /// events.push(Alloc(request));
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Free(request));
+    ///
+    /// let report = events.into_report();
+    /// assert_eq!(2, report.allocs);
+    /// assert_eq!(0x20, report.max_memory_used);
+    /// assert_eq!(1, report.violations.len());
+    ///
+    /// println!("{}", report);
+    /// ```
+    pub fn into_report(self) -> AllocationReport {
+        let mut machine = Machine::default();
+
+        let mut max_memory_used = 0usize;
+        let mut timeline = Vec::with_capacity(self.len());
+        let mut violations = Vec::new();
+
+        self.replay(&mut machine, self.as_slice(), |_, machine, e| {
+            violations.extend(e);
+            max_memory_used = usize::max(machine.memory_used, max_memory_used);
+            timeline.push(machine.memory_used);
+            true
+        });
+
+        for alloc in machine.trailing_regions() {
+            violations.push(Violation::Leaked { alloc });
+        }
+
+        AllocationReport {
+            events: self.len(),
+            allocs: self.allocs(),
+            reallocs: self.reallocs(),
+            frees: self.frees(),
+            failed: self.iter_failed().count(),
+            max_memory_used,
+            timeline,
+            violations,
+        }
+    }
+
+    /// Record the allocation profile of these events, which can be compared
+    /// against later through [Events::assert_allocation_profile_matches].
+    ///
+    /// The tolerance of the returned profile is `0.0`.
+    ///
+    /// See [AllocationProfile] for more documentation.
+    pub fn allocation_profile(&self) -> AllocationProfile {
+        let mut profile = AllocationProfile::default();
+
+        for request in self.data.iter().filter_map(alloc_request) {
+            let region = request.region;
+            // Note: sizes above the largest power of two can't be rounded up.
+            let bucket = region.size.checked_next_power_of_two();
+            profile.alloc_count += 1;
+            *profile
+                .size_buckets
+                .entry(bucket.unwrap_or(usize::MAX))
+                .or_default() += 1;
+            *profile.alignments.entry(region.align).or_default() += 1;
+        }
+
+        profile
+    }
+
+    /// Assert that the allocation profile of these events matches the given
+    /// golden `profile`, within the tolerance configured in it.
+    ///
+    /// This enables regression testing of how a piece of code allocates, by
+    /// recording its profile once through [Events::allocation_profile] and
+    /// asserting that it doesn't change.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the profiles don't match, listing every count which differs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// for n in 0..10 {
+    ///     events.push(Alloc(Request::without_backtrace(Region::new((n * 0x10).into(), 0x10, 8))));
+    /// }
+    ///
+    /// let mut profile = events.allocation_profile();
+    /// events.assert_allocation_profile_matches(&profile);
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x100.into(), 0x10, 8))));
+    ///
+    /// // One more allocation is within a tolerance of 10%.
+    /// profile.tolerance = 0.1;
+    /// events.assert_allocation_profile_matches(&profile);
+    /// ```
+    ///
+    /// ```rust,should_panic
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 8))));
+    /// let profile = events.allocation_profile();
+    ///
+    /// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 8))));
+    /// events.assert_allocation_profile_matches(&profile);
+    /// ```
+    pub fn assert_allocation_profile_matches(&self, profile: &AllocationProfile) {
+        crate::with_muted(|| {
+            let mismatches = profile.mismatches(&self.allocation_profile());
+
+            if mismatches.is_empty() {
+                return;
+            }
+
+            let mut message = format!(
+                "expected the allocation profile to match, but got {} mismatches",
+                mismatches.len()
+            );
+
+            for mismatch in mismatches {
+                message.push_str("\n  ");
+                message.push_str(&mismatch);
+            }
+
+            panic!("{}", message);
+        });
+    }
+
+    /// Calculate the ratio of live bytes to the total number of bytes ever
+    /// allocated, after each event in this history.
+    ///
+    /// Values close to `1.0` indicate that little allocated memory has been
+    /// released, while values close to `0.0` indicate that most of it has been
+    /// freed or reused. The ratio is `0.0` until anything has been allocated.
+    ///
+    /// Returns the first violation encountered if the history is not sound.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Events, Region, Request};
+    /// let mut events = Events::new();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// events.push(Alloc(request));
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x20.into(), 0x30, 1));
+    /// events.push(Alloc(request));
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x20.into(), 0x30, 1));
+    /// events.push(Free(request));
+    ///
+    /// assert_eq!(vec![1.0, 1.0, 0.25], events.alloc_pressure_at_each_event().unwrap());
+    /// ```
+    pub fn alloc_pressure_at_each_event(&self) -> Result<Vec<f64>, Violation> {
+        let mut machine = Machine::default();
+
+        let mut total = 0usize;
+        let mut pressure = Vec::with_capacity(self.len());
+
+        for event in self.as_slice() {
+            machine.push(event)?;
+
+            match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                    total = total.saturating_add(request.region.size);
+                }
+                Event::Realloc(realloc) => {
+                    total = total.saturating_add(realloc.alloc.size);
+                }
+                _ => (),
+            }
+
+            if total == 0 {
+                pressure.push(0.0);
+            } else {
+                pressure.push(machine.memory_used as f64 / total as f64);
+            }
+        }
+
+        Ok(pressure)
+    }
+}
+
+/// Events partitioned by their kind.
+///
+/// Constructed through [Events::collect_by_type].
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct CollectedEvents<'a> {
+    /// Requests of all [Alloc][Event::Alloc] events.
+    pub allocs: Vec<&'a Request>,
+    /// Payloads of all [AllocZeroed][Event::AllocZeroed] events.
+    pub alloc_zeroed: Vec<&'a AllocZeroed>,
+    /// Requests of all [Free][Event::Free] events.
+    pub frees: Vec<&'a Request>,
+    /// Payloads of all [Realloc][Event::Realloc] events.
+    pub reallocs: Vec<&'a Realloc>,
+    /// All failed events, see [Event::is_failed].
+    pub failed: Vec<&'a Event>,
+}
+
+impl CollectedEvents<'_> {
+    /// Count the number of allocations, including zeroed allocations.
+    ///
+    /// See [Events::allocs] for more documentation.
+    pub fn allocs(&self) -> usize {
+        self.allocs.len() + self.alloc_zeroed.len()
+    }
+
+    /// Count the number of reallocations.
+    ///
+    /// See [Events::reallocs] for more documentation.
+    pub fn reallocs(&self) -> usize {
+        self.reallocs.len()
+    }
+
+    /// Count the number of frees.
+    ///
+    /// See [Events::frees] for more documentation.
+    pub fn frees(&self) -> usize {
+        self.frees.len()
+    }
+
+    /// Iterate over all failed events.
+    ///
+    /// See [Events::iter_failed] for more documentation.
+    pub fn iter_failed(&self) -> impl Iterator<Item = &Event> + '_ {
+        self.failed.iter().copied()
+    }
+
+    /// Iterate over the payload of all zeroed allocations.
+    ///
+    /// See [Events::iter_alloc_zeroed] for more documentation.
+    pub fn iter_alloc_zeroed(&self) -> impl Iterator<Item = &AllocZeroed> + '_ {
+        self.alloc_zeroed.iter().copied()
+    }
+}
+
+/// Get the request of an [Alloc][Event::Alloc] or
+/// [AllocZeroed][Event::AllocZeroed] event.
+fn alloc_request(event: &Event) -> Option<&Request> {
+    match event {
+        Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => Some(request),
+        _ => None,
+    }
+}
+
+/// Get the request of a [Free][Event::Free] event.
+fn free_request(event: &Event) -> Option<&Request> {
+    match event {
+        Event::Free(request) => Some(request),
+        _ => None,
+    }
+}
+
+/// Get the request for the region allocated by the given event, if any.
+fn allocated_request(event: &Event) -> Option<Request> {
+    match event {
+        Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+            Some(request.clone())
+        }
+        Event::Realloc(realloc) => Some(realloc.alloc()),
+        _ => None,
     }
 }
 
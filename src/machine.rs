@@ -2,6 +2,7 @@
 
 use std::{
     collections::{btree_map as map, BTreeMap},
+    convert::TryFrom,
     fmt,
 };
 
@@ -35,6 +36,95 @@ impl Region {
     pub fn is_same_region_as(self, other: Self) -> bool {
         self.ptr == other.ptr && self.size == other.size
     }
+
+    /// Compute the number of padding bytes needed at `offset` within the
+    /// region to reach an address which is aligned to `inner_align`.
+    ///
+    /// Returns `None` if the aligned address doesn't lie within the region, or
+    /// if `inner_align` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::Region;
+    ///
+    /// let region = Region::new(0x10.into(), 0x10, 4);
+    ///
+    /// assert_eq!(Some(0), region.alignment_padding_from(0, 8));
+    /// assert_eq!(Some(7), region.alignment_padding_from(1, 8));
+    /// assert_eq!(Some(4), region.alignment_padding_from(0x4, 8));
+    /// // Note: the next 16-byte aligned address is outside of the region.
+    /// assert_eq!(None, region.alignment_padding_from(0x4, 16));
+    /// assert_eq!(None, region.alignment_padding_from(0, 0));
+    /// ```
+    pub fn alignment_padding_from(self, offset: usize, inner_align: usize) -> Option<usize> {
+        if inner_align == 0 {
+            return None;
+        }
+
+        let addr = self.ptr.0.checked_add(offset)?;
+        let padding = (inner_align - addr % inner_align) % inner_align;
+
+        if offset.checked_add(padding)? >= self.size {
+            return None;
+        }
+
+        Some(padding)
+    }
+
+    /// Split the region into two contiguous regions at the given offset, where
+    /// the first covers `[ptr, ptr + offset)` and the second covers
+    /// `[ptr + offset, ptr + size)`.
+    ///
+    /// Both regions keep the alignment of the original region, note that this
+    /// means that the second region might not be aligned as indicated unless
+    /// `offset` is a multiple of the alignment.
+    ///
+    /// Returns `None` if `offset` is not smaller than the size of the region.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::Region;
+    ///
+    /// let region = Region::new(0x10.into(), 0x10, 4);
+    ///
+    /// let (head, tail) = region.split(0x4).unwrap();
+    /// assert_eq!(Region::new(0x10.into(), 0x4, 4), head);
+    /// assert_eq!(Region::new(0x14.into(), 0xc, 4), tail);
+    ///
+    /// assert!(region.split(0x10).is_none());
+    /// ```
+    pub fn split(self, offset: usize) -> Option<(Region, Region)> {
+        if offset >= self.size {
+            return None;
+        }
+
+        let head = Region::new(self.ptr, offset, self.align);
+        let tail = Region::new(
+            self.ptr.saturating_add(offset),
+            self.size - offset,
+            self.align,
+        );
+        Some((head, tail))
+    }
+
+    /// Format the region in a compact form suitable for single-line output,
+    /// like `0x1234[16B@8]`.
+    ///
+    /// See the [Display][fmt::Display] implementation for the verbose form.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::Region;
+    ///
+    /// let region = Region::new(0x1234.into(), 16, 8);
+    /// assert_eq!("0x1234[16B@8]", region.fmt_compact());
+    /// ```
+    pub fn fmt_compact(self) -> String {
+        format!("{}[{}B@{}]", self.ptr, self.size, self.align)
+    }
 }
 
 impl fmt::Display for Region {
@@ -51,12 +141,18 @@ impl fmt::Display for Region {
 }
 
 /// Fake machine implementation to validate an allocation history.
-#[derive(Default)]
+#[derive(Debug, Default)]
 pub struct Machine {
     /// Used memory regions.
     regions: BTreeMap<Pointer, Request>,
     /// Current memory used according to allocations.
     pub memory_used: usize,
+    /// Number of allocations processed.
+    allocation_count: usize,
+    /// Number of deallocations processed.
+    free_count: usize,
+    /// Maximum number of simultaneously live regions permitted.
+    max_live_regions: Option<usize>,
 }
 
 impl Machine {
@@ -96,6 +192,23 @@ impl Machine {
     /// ));
     /// ```
     ///
+    /// Check for an allocation overlapping with an existing region:
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Request, Region, Machine, Violation};
+    ///
+    /// let mut machine = Machine::default();
+    ///
+    /// let request = Request::without_backtrace(Region::new(150.into(), 50, 1));
+    /// assert!(machine.push(&Alloc(request)).is_ok());
+    ///
+    /// let request = Request::without_backtrace(Region::new(100.into(), 100, 1));
+    /// assert!(matches!(
+    ///     machine.push(&Alloc(request)).unwrap_err(),
+    ///     Violation::ConflictingAlloc { .. }
+    /// ));
+    /// ```
+    ///
     /// Tries to deallocate part of other region:
     ///
     /// ```rust
@@ -120,12 +233,61 @@ impl Machine {
     /// ));
     /// ```
     pub fn push(&mut self, event: &Event) -> Result<(), Violation> {
+        self.apply(event, None)
+    }
+
+    /// Push an event into the machine like [Machine::push], and describe the
+    /// changes it caused to the state of the machine.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Request, Region, Machine, Realloc};
+    ///
+    /// let mut machine = Machine::default();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// let change = machine.push_and_collect(&Alloc(request)).unwrap();
+    /// assert_eq!(1, change.added_regions.len());
+    /// assert_eq!(0x10, change.memory_delta);
+    ///
+    /// let change = machine.push_and_collect(&Realloc(Realloc::without_backtrace(
+    ///     Some(true),
+    ///     Region::new(0x10.into(), 0x10, 1),
+    ///     Region::new(0x40.into(), 0x08, 1),
+    /// ))).unwrap();
+    /// assert_eq!(1, change.added_regions.len());
+    /// assert_eq!(1, change.removed_regions.len());
+    /// assert_eq!(-0x08, change.memory_delta);
+    ///
+    /// assert!(machine.push_and_collect(&AllocFailed).unwrap().is_noop());
+    /// ```
+    pub fn push_and_collect(&mut self, event: &Event) -> Result<MachineChange, Violation> {
+        let mut change = MachineChange::default();
+        self.apply(event, Some(&mut change))?;
+        Ok(change)
+    }
+
+    /// Apply an event to the machine, optionally recording the changes made.
+    fn apply(
+        &mut self,
+        event: &Event,
+        mut change: Option<&mut MachineChange>,
+    ) -> Result<(), Violation> {
         match event {
             Event::Alloc(requested) => {
                 self.alloc(requested)?;
+
+                if let Some(change) = change {
+                    change.added(requested.clone());
+                }
             }
             Event::Free(requested) => {
-                self.free(requested)?;
+                let removed = self.free(requested)?;
+
+                if let Some(change) = change {
+                    change.removed(removed);
+                }
             }
             Event::AllocZeroed(AllocZeroed { is_zeroed, request }) => {
                 if let Some(false) = is_zeroed {
@@ -135,6 +297,10 @@ impl Machine {
                 }
 
                 self.alloc(request)?;
+
+                if let Some(change) = change {
+                    change.added(request.clone());
+                }
             }
             Event::Realloc(realloc) => {
                 if let Some(false) = realloc.is_relocated {
@@ -143,8 +309,18 @@ impl Machine {
                     });
                 }
 
-                self.free(&realloc.free())?;
-                self.alloc(&realloc.alloc())?;
+                let removed = self.free(&realloc.free())?;
+
+                if let Some(change) = change.as_mut() {
+                    change.removed(removed);
+                }
+
+                let alloc = realloc.alloc();
+                self.alloc(&alloc)?;
+
+                if let Some(change) = change {
+                    change.added(alloc);
+                }
             }
             Event::ReallocNull(realloc) => {
                 return Err(Violation::ReallocNull {
@@ -163,6 +339,84 @@ impl Machine {
         Ok(())
     }
 
+    /// Push an event into the machine like [Machine::push], but apply its side
+    /// effects even if it results in a violation.
+    ///
+    /// The violation is still returned so that it can be reported. This is
+    /// useful to analyze the state of the machine after an error has occurred,
+    /// as if the program kept going. Allocations are added to the live
+    /// regions, replacing any region at the same pointer, and deallocations
+    /// remove any region at the pointer being freed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Request, Region, Machine, Violation};
+    ///
+    /// let mut machine = Machine::default();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// assert!(machine.push(&Alloc(request)).is_ok());
+    ///
+    /// // Note: frees only part of the region.
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x08, 1));
+    /// assert!(matches!(
+    ///     machine.push_ignore_violation(&Free(request)).unwrap_err(),
+    ///     Violation::IncompleteFree { .. }
+    /// ));
+    ///
+    /// assert!(machine.trailing_regions().is_empty());
+    /// assert_eq!(0, machine.memory_used);
+    /// assert!(machine.is_balanced());
+    /// ```
+    pub fn push_ignore_violation(&mut self, event: &Event) -> Result<(), Violation> {
+        let result = self.push(event);
+
+        // Note: the forced operations are idempotent, so any effects which
+        // were applied before the violation was raised are not repeated.
+        if result.is_err() {
+            match event {
+                Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                    self.force_alloc(request.clone());
+                }
+                Event::Free(request) => {
+                    self.force_free(request.region);
+                }
+                Event::Realloc(realloc) => {
+                    self.force_free(realloc.free);
+                    self.force_alloc(realloc.alloc());
+                }
+                _ => (),
+            }
+        }
+
+        result
+    }
+
+    /// Process an allocation without checking for violations.
+    fn force_alloc(&mut self, request: Request) {
+        if let Some(existing) = self.regions.get(&request.region.ptr) {
+            if existing.region.is_same_region_as(request.region) {
+                return;
+            }
+        }
+
+        self.memory_used = self.memory_used.saturating_add(request.region.size);
+        self.allocation_count += 1;
+
+        if let Some(existing) = self.regions.insert(request.region.ptr, request) {
+            self.memory_used = self.memory_used.saturating_sub(existing.region.size);
+        }
+    }
+
+    /// Process a free without checking for violations.
+    fn force_free(&mut self, region: Region) {
+        if let Some(existing) = self.regions.remove(&region.ptr) {
+            self.memory_used = self.memory_used.saturating_sub(existing.region.size);
+            self.free_count += 1;
+        }
+    }
+
     /// Process an allocation.
     fn alloc(&mut self, request: &Request) -> Result<(), Violation> {
         if !request.region.ptr.is_aligned_with(request.region.align) {
@@ -171,14 +425,28 @@ impl Machine {
             });
         }
 
-        if let Some(existing) = find_region_overlaps(&self.regions, request.region).next() {
+        // Note: zero-sized regions are considered to occupy one byte, since no
+        // two live regions can share the same pointer.
+        let start = request.region.ptr;
+        let end = start.saturating_add(usize::max(request.region.size, 1));
+
+        if let Some(existing) = find_region_overlaps(&self.regions, start, end).next() {
             return Err(Violation::ConflictingAlloc {
                 request: request.clone(),
-                existing,
+                existing: existing.clone(),
             });
         }
 
+        if let Some(max) = self.max_live_regions {
+            let count = self.regions.len() + 1;
+
+            if count > max {
+                return Err(Violation::TooManyLiveRegions { count, max });
+            }
+        }
+
         self.memory_used = self.memory_used.saturating_add(request.region.size);
+        self.allocation_count += 1;
 
         let existing = self.regions.insert(request.region.ptr, request.clone());
 
@@ -186,8 +454,8 @@ impl Machine {
         Ok(())
     }
 
-    /// Process a free.
-    fn free(&mut self, request: &Request) -> Result<(), Violation> {
+    /// Process a free, returning the request of the region which was freed.
+    fn free(&mut self, request: &Request) -> Result<Request, Violation> {
         let entry = if let map::Entry::Occupied(entry) = self.regions.entry(request.region.ptr) {
             entry
         } else {
@@ -214,7 +482,99 @@ impl Machine {
 
         let (_, region) = entry.remove_entry();
         self.memory_used = self.memory_used.saturating_sub(region.region.size);
-        Ok(())
+        self.free_count += 1;
+        Ok(region)
+    }
+
+    /// Limit the number of regions which can be live at the same time.
+    ///
+    /// Allocations which would exceed the limit produce a
+    /// [Violation::TooManyLiveRegions] and are not processed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Request, Region, Machine, Violation};
+    ///
+    /// let mut machine = Machine::default();
+    /// machine.set_max_live_regions(1);
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// assert!(machine.push(&Alloc(request)).is_ok());
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x20.into(), 0x10, 1));
+    /// assert!(matches!(
+    ///     machine.push(&Alloc(request)).unwrap_err(),
+    ///     Violation::TooManyLiveRegions { count: 2, max: 1 }
+    /// ));
+    /// ```
+    pub fn set_max_live_regions(&mut self, max: usize) {
+        self.max_live_regions = Some(max);
+    }
+
+    /// The number of allocations successfully processed by the machine.
+    ///
+    /// A reallocation counts as both an allocation and a deallocation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Request, Region, Machine};
+    ///
+    /// let mut machine = Machine::default();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0.into(), 2, 1));
+    /// assert!(machine.push(&Alloc(request)).is_ok());
+    /// assert_eq!(1, machine.allocation_count());
+    /// assert_eq!(0, machine.free_count());
+    /// assert!(!machine.is_balanced());
+    ///
+    /// let request = Request::without_backtrace(Region::new(0.into(), 2, 1));
+    /// assert!(machine.push(&Free(request)).is_ok());
+    /// assert_eq!(1, machine.allocation_count());
+    /// assert_eq!(1, machine.free_count());
+    /// assert!(machine.is_balanced());
+    /// ```
+    pub fn allocation_count(&self) -> usize {
+        self.allocation_count
+    }
+
+    /// The number of deallocations successfully processed by the machine.
+    ///
+    /// See [Machine::allocation_count] for more documentation.
+    pub fn free_count(&self) -> usize {
+        self.free_count
+    }
+
+    /// Test if the number of processed allocations matches the number of
+    /// processed deallocations.
+    ///
+    /// See [Machine::allocation_count] for more documentation.
+    pub fn is_balanced(&self) -> bool {
+        self.allocation_count == self.free_count
+    }
+
+    /// Find all live regions which overlap with the range `[start, end)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::{Event::*, Request, Region, Machine};
+    ///
+    /// let mut machine = Machine::default();
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+    /// assert!(machine.push(&Alloc(request)).is_ok());
+    ///
+    /// let request = Request::without_backtrace(Region::new(0x30.into(), 0x10, 1));
+    /// assert!(machine.push(&Alloc(request)).is_ok());
+    ///
+    /// assert_eq!(1, machine.regions_overlapping(0x00.into(), 0x11.into()).len());
+    /// assert_eq!(2, machine.regions_overlapping(0x1f.into(), 0x31.into()).len());
+    /// assert_eq!(0, machine.regions_overlapping(0x20.into(), 0x30.into()).len());
+    /// ```
+    pub fn regions_overlapping(&self, start: Pointer, end: Pointer) -> Vec<&Request> {
+        find_region_overlaps(&self.regions, start, end).collect()
     }
 
     /// Access all trailing regions (ones which have not been deallocated).
@@ -223,18 +583,60 @@ impl Machine {
     }
 }
 
-/// Utility function to find overlapping regions.
+/// The changes caused by a single event pushed into a [Machine].
+///
+/// See [Machine::push_and_collect].
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct MachineChange {
+    /// Regions which were allocated.
+    pub added_regions: Vec<Request>,
+    /// Regions which were freed, as they were originally allocated.
+    pub removed_regions: Vec<Request>,
+    /// The change in memory used.
+    pub memory_delta: isize,
+}
+
+impl MachineChange {
+    /// Test if the event didn't change the state of the machine, like failed
+    /// allocations.
+    pub fn is_noop(&self) -> bool {
+        self.added_regions.is_empty() && self.removed_regions.is_empty()
+    }
+
+    /// Record an allocated region.
+    fn added(&mut self, request: Request) {
+        let size = isize::try_from(request.region.size).unwrap_or(isize::MAX);
+        self.memory_delta = self.memory_delta.saturating_add(size);
+        self.added_regions.push(request);
+    }
+
+    /// Record a freed region.
+    fn removed(&mut self, request: Request) {
+        let size = isize::try_from(request.region.size).unwrap_or(isize::MAX);
+        self.memory_delta = self.memory_delta.saturating_sub(size);
+        self.removed_regions.push(request);
+    }
+}
+
+/// Utility function to find regions overlapping with the range `[start, end)`.
+///
+/// Live regions never overlap each other, so only the last region starting
+/// before `start` can extend into the range.
 fn find_region_overlaps(
     regions: &BTreeMap<Pointer, Request>,
-    needle: Region,
-) -> impl Iterator<Item = Request> + '_ {
+    start: Pointer,
+    end: Pointer,
+) -> impl Iterator<Item = &Request> + '_ {
+    let end = Pointer::max(start, end);
+
     let head = regions
-        .range(..=needle.ptr)
-        .take_while(move |(_, r)| r.region.overlaps(needle));
+        .range(..start)
+        .next_back()
+        .map(|(_, r)| r)
+        .filter(move |r| start < r.region.ptr.saturating_add(r.region.size));
 
-    let tail = regions
-        .range(needle.ptr..)
-        .take_while(move |(_, r)| r.region.overlaps(needle));
+    let tail = regions.range(start..end).map(|(_, r)| r);
 
-    head.chain(tail).map(|(_, r)| r.clone())
+    head.into_iter().chain(tail)
 }
@@ -0,0 +1,118 @@
+//! User-defined policies to enforce over an allocation history.
+
+use crate::{AllocZeroed, Event, Machine, Violation};
+
+/// A policy which is checked for each event, in addition to the invariants
+/// enforced by the [Machine].
+///
+/// See [Events::validate_with_policy][crate::Events::validate_with_policy].
+///
+/// # Examples
+///
+/// ```rust
+/// use checkers::{AllocationPolicy, Event, Machine, Violation};
+///
+/// /// Policy forbidding zeroed allocations.
+/// struct NoZeroed;
+///
+/// impl AllocationPolicy for NoZeroed {
+///     fn check(&self, event: &Event, _: &Machine) -> Option<Violation> {
+///         match event {
+///             Event::AllocZeroed(alloc) => Some(Violation::NonZeroedAlloc {
+///                 alloc: alloc.request.clone(),
+///             }),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait AllocationPolicy {
+    /// Check a single event. The `machine` has already processed the event
+    /// when this is called.
+    fn check(&self, event: &Event, machine: &Machine) -> Option<Violation>;
+}
+
+/// A policy limiting the size of individual allocations.
+///
+/// Reallocations are checked against the size of the region they allocate.
+///
+/// # Examples
+///
+/// ```rust
+/// use checkers::{Event::*, Events, MaxSizePolicy, Region, Request, Violation};
+///
+/// let mut events = Events::new();
+/// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x100, 1))));
+/// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x100, 1))));
+///
+/// let mut errors = Vec::new();
+/// events.validate_with_policy(&mut errors, &MaxSizePolicy(0x80));
+///
+/// assert_eq!(1, errors.len());
+/// assert!(matches!(errors[0], Violation::OversizedAlloc { max_size: 0x80, .. }));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MaxSizePolicy(pub usize);
+
+impl AllocationPolicy for MaxSizePolicy {
+    fn check(&self, event: &Event, _: &Machine) -> Option<Violation> {
+        let alloc = match event {
+            Event::Alloc(request) | Event::AllocZeroed(AllocZeroed { request, .. }) => {
+                request.clone()
+            }
+            Event::Realloc(realloc) => realloc.alloc(),
+            _ => return None,
+        };
+
+        if alloc.region.size <= self.0 {
+            return None;
+        }
+
+        Some(Violation::OversizedAlloc {
+            alloc,
+            max_size: self.0,
+        })
+    }
+}
+
+/// A policy limiting the number of allocations performed.
+///
+/// A violation is raised for every allocation performed beyond the limit.
+/// Reallocations count as allocations, see [Machine::allocation_count].
+///
+/// # Examples
+///
+/// ```rust
+/// use checkers::{Event::*, Events, MaxCountPolicy, Region, Request, Violation};
+///
+/// let mut events = Events::new();
+/// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+/// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+/// events.push(Free(Request::without_backtrace(Region::new(0x10.into(), 0x10, 1))));
+/// events.push(Free(Request::without_backtrace(Region::new(0x20.into(), 0x10, 1))));
+///
+/// let mut errors = Vec::new();
+/// events.validate_with_policy(&mut errors, &MaxCountPolicy(1));
+///
+/// assert_eq!(1, errors.len());
+/// assert!(matches!(errors[0], Violation::TooManyAllocs { count: 2, max: 1 }));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MaxCountPolicy(pub usize);
+
+impl AllocationPolicy for MaxCountPolicy {
+    fn check(&self, event: &Event, machine: &Machine) -> Option<Violation> {
+        match event {
+            Event::Alloc(..) | Event::AllocZeroed(..) | Event::Realloc(..) => (),
+            _ => return None,
+        }
+
+        let count = machine.allocation_count();
+
+        if count <= self.0 {
+            return None;
+        }
+
+        Some(Violation::TooManyAllocs { count, max: self.0 })
+    }
+}
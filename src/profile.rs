@@ -0,0 +1,107 @@
+//! Allocation profiles, used for golden-profile regression testing.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A profile of the allocations performed in an event history.
+///
+/// Constructed through
+/// [Events::allocation_profile][crate::Events::allocation_profile] and
+/// compared against through
+/// [Events::assert_allocation_profile_matches][crate::Events::assert_allocation_profile_matches].
+///
+/// With the `serde` feature enabled the profile can be serialized, so that it
+/// can be recorded once and stored alongside the tests which load it.
+///
+/// # Examples
+///
+/// ```rust
+/// use checkers::{Event::*, Events, Region, Request};
+/// let mut events = Events::new();
+///
+/// events.push(Alloc(Request::without_backtrace(Region::new(0x10.into(), 0x10, 8))));
+/// events.push(Alloc(Request::without_backtrace(Region::new(0x20.into(), 0x0c, 4))));
+/// events.push(Alloc(Request::without_backtrace(Region::new(0x40.into(), 0x30, 8))));
+///
+/// let profile = events.allocation_profile();
+/// assert_eq!(3, profile.alloc_count);
+/// assert_eq!(Some(&2), profile.size_buckets.get(&0x10));
+/// assert_eq!(Some(&1), profile.size_buckets.get(&0x40));
+/// assert_eq!(Some(&2), profile.alignments.get(&8));
+/// assert_eq!(Some(&1), profile.alignments.get(&4));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AllocationProfile {
+    /// The number of allocations, including zeroed allocations.
+    pub alloc_count: usize,
+    /// The number of allocations by size, where each size is rounded up to
+    /// the next power of two. Sizes larger than the largest power of two are
+    /// counted under `usize::MAX`.
+    pub size_buckets: BTreeMap<usize, usize>,
+    /// The number of allocations by alignment.
+    pub alignments: BTreeMap<usize, usize>,
+    /// The relative tolerance permitted when comparing counts against this
+    /// profile, where `0.1` permits each count to differ by up to 10%.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tolerance: f64,
+}
+
+impl AllocationProfile {
+    /// Collect a description of every count in `actual` which doesn't match
+    /// this profile within its tolerance.
+    pub(crate) fn mismatches(&self, actual: &AllocationProfile) -> Vec<String> {
+        let mut mismatches = Vec::new();
+
+        if !self.within_tolerance(self.alloc_count, actual.alloc_count) {
+            mismatches.push(format!(
+                "expected {} allocations, but got {}",
+                self.alloc_count, actual.alloc_count
+            ));
+        }
+
+        self.bucket_mismatches(
+            "allocations of size up to",
+            &self.size_buckets,
+            &actual.size_buckets,
+            &mut mismatches,
+        );
+
+        self.bucket_mismatches(
+            "allocations with alignment",
+            &self.alignments,
+            &actual.alignments,
+            &mut mismatches,
+        );
+
+        mismatches
+    }
+
+    fn bucket_mismatches(
+        &self,
+        what: &str,
+        expected: &BTreeMap<usize, usize>,
+        actual: &BTreeMap<usize, usize>,
+        mismatches: &mut Vec<String>,
+    ) {
+        let keys = expected
+            .keys()
+            .chain(actual.keys())
+            .collect::<BTreeSet<_>>();
+
+        for key in keys {
+            let expected = expected.get(key).copied().unwrap_or_default();
+            let actual = actual.get(key).copied().unwrap_or_default();
+
+            if !self.within_tolerance(expected, actual) {
+                mismatches.push(format!(
+                    "expected {} {} {}, but got {}",
+                    expected, what, key, actual
+                ));
+            }
+        }
+    }
+
+    fn within_tolerance(&self, expected: usize, actual: usize) -> bool {
+        expected.abs_diff(actual) as f64 <= expected as f64 * self.tolerance
+    }
+}
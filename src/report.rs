@@ -0,0 +1,66 @@
+//! Human-readable reports of an allocation history.
+
+use std::fmt;
+
+use crate::Violation;
+
+/// A report summarizing an allocation history, suitable for including in bug
+/// reports.
+///
+/// Constructed through [Events::into_report][crate::Events::into_report].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct AllocationReport {
+    /// The number of events in the history.
+    pub events: usize,
+    /// The number of allocations, including zeroed allocations.
+    pub allocs: usize,
+    /// The number of reallocations.
+    pub reallocs: usize,
+    /// The number of deallocations.
+    pub frees: usize,
+    /// The number of failed allocations.
+    pub failed: usize,
+    /// The maximum amount of memory used at any point in the history.
+    pub max_memory_used: usize,
+    /// The amount of memory used after each event.
+    pub timeline: Vec<usize>,
+    /// All violations found while validating the history.
+    pub violations: Vec<Violation>,
+}
+
+impl fmt::Display for AllocationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Summary:")?;
+        writeln!(f, "  events: {}", self.events)?;
+        writeln!(f, "  allocations: {}", self.allocs)?;
+        writeln!(f, "  reallocations: {}", self.reallocs)?;
+        writeln!(f, "  deallocations: {}", self.frees)?;
+        writeln!(f, "  failed allocations: {}", self.failed)?;
+        writeln!(f, "  max memory used: {} bytes", self.max_memory_used)?;
+
+        let memory_used = self.timeline.last().copied().unwrap_or_default();
+        writeln!(f, "  memory used at end: {} bytes", memory_used)?;
+
+        // Note: only list the events where memory usage changed.
+        writeln!(f, "Timeline:")?;
+
+        let mut previous = 0;
+
+        for (index, &memory_used) in self.timeline.iter().enumerate() {
+            if memory_used != previous {
+                writeln!(f, "  #{}: {} bytes", index, memory_used)?;
+                previous = memory_used;
+            }
+        }
+
+        write!(f, "Violations: {}", self.violations.len())?;
+
+        for violation in &self.violations {
+            writeln!(f)?;
+            write!(f, "  {}", violation.summary_line())?;
+        }
+
+        Ok(())
+    }
+}
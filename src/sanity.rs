@@ -0,0 +1,77 @@
+//! Cheap sanity checks over the events themselves.
+
+use std::fmt;
+
+use crate::Region;
+
+/// A warning produced by [Events::sanity_check][crate::Events::sanity_check].
+///
+/// Unlike a [Violation][crate::Violation] these are not necessarily errors,
+/// but they indicate suspicious or corrupt events. Each warning carries the
+/// index of the event it was produced for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SanityWarning {
+    /// A region of size zero was allocated.
+    ZeroSizeAlloc {
+        /// The index of the event.
+        index: usize,
+        /// The allocated region.
+        region: Region,
+    },
+    /// A region of size zero was freed.
+    ZeroSizeFree {
+        /// The index of the event.
+        index: usize,
+        /// The freed region.
+        region: Region,
+    },
+    /// A region has an alignment of zero.
+    ZeroAlignment {
+        /// The index of the event.
+        index: usize,
+        /// The region.
+        region: Region,
+    },
+    /// A region has an alignment which is not a power of two.
+    NonPowerOfTwoAlignment {
+        /// The index of the event.
+        index: usize,
+        /// The region.
+        region: Region,
+    },
+    /// A region has a size larger than `isize::MAX`, which no valid layout
+    /// can have.
+    SizeOverflow {
+        /// The index of the event.
+        index: usize,
+        /// The region.
+        region: Region,
+    },
+}
+
+impl fmt::Display for SanityWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroSizeAlloc { index, region } => {
+                write!(f, "#{}: Zero-sized allocation ({})", index, region)
+            }
+            Self::ZeroSizeFree { index, region } => {
+                write!(f, "#{}: Zero-sized deallocation ({})", index, region)
+            }
+            Self::ZeroAlignment { index, region } => {
+                write!(f, "#{}: Region with zero alignment ({})", index, region)
+            }
+            Self::NonPowerOfTwoAlignment { index, region } => write!(
+                f,
+                "#{}: Region with an alignment which is not a power of two ({})",
+                index, region
+            ),
+            Self::SizeOverflow { index, region } => write!(
+                f,
+                "#{}: Region with a size that overflows isize ({})",
+                index, region
+            ),
+        }
+    }
+}
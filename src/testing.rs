@@ -0,0 +1,92 @@
+//! Utilities for testing checkers itself, or code built on top of its
+//! [Machine][crate::Machine] and [Events][crate::Events].
+//!
+//! This module is only available with the `testing` feature.
+
+use crate::{Region, Request};
+
+/// A fake allocator producing [Request]s without touching any real memory.
+///
+/// Pointers are handed out in increasing order like in a bump allocator. They
+/// are never null, satisfy the requested alignment and never overlap with a
+/// previously produced region. Since memory is never reused, every request
+/// produced has a unique pointer.
+///
+/// # Examples
+///
+/// ```rust
+/// use checkers::{Event::*, Events};
+/// use checkers::testing::MockAllocator;
+///
+/// let mut allocator = MockAllocator::new();
+///
+/// let a = allocator.alloc_request(0x10, 8);
+/// let b = allocator.alloc_request(0x10, 8);
+/// assert_ne!(a.region.ptr, b.region.ptr);
+///
+/// let mut events = Events::new();
+/// events.push(Alloc(a.clone()));
+/// events.push(Alloc(b.clone()));
+/// events.push(Free(a));
+/// events.push(Free(b));
+///
+/// let mut errors = Vec::new();
+/// events.validate(&mut errors);
+/// assert!(errors.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockAllocator {
+    next: usize,
+}
+
+impl MockAllocator {
+    /// Construct a new mock allocator.
+    pub const fn new() -> Self {
+        Self { next: 1 }
+    }
+
+    /// Produce the region of a new allocation with the given size and
+    /// alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::testing::MockAllocator;
+    ///
+    /// let mut allocator = MockAllocator::new();
+    ///
+    /// let region = allocator.alloc_region(0x10, 4);
+    /// assert_eq!(0x10, region.size);
+    /// assert!(region.ptr.is_aligned_with(4));
+    /// ```
+    pub fn alloc_region(&mut self, size: usize, align: usize) -> Region {
+        let align = usize::max(align, 1);
+        let ptr = self.next + (align - self.next % align) % align;
+        // Note: zero-sized regions still occupy a unique address.
+        self.next = ptr + usize::max(size, 1);
+        Region::new(ptr.into(), size, align)
+    }
+
+    /// Produce a new allocation with the given size and alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use checkers::testing::MockAllocator;
+    ///
+    /// let mut allocator = MockAllocator::new();
+    ///
+    /// let request = allocator.alloc_request(0x10, 8);
+    /// assert_ne!(checkers::Pointer::from(0), request.region.ptr);
+    /// assert!(request.backtrace.is_none());
+    /// ```
+    pub fn alloc_request(&mut self, size: usize, align: usize) -> Request {
+        Request::without_backtrace(self.alloc_region(size, align))
+    }
+}
+
+impl Default for MockAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,7 +3,7 @@
 /// # Safety
 ///
 /// `ptr` needs to be non-null and initialized to the length specified in `len`.
-#[cfg(feature = "zeroed")]
+#[cfg(all(feature = "zeroed", not(feature = "no-ub")))]
 pub(crate) unsafe fn is_zeroed_ptr(ptr: *const u8, len: usize) -> bool {
     debug_assert!(!ptr.is_null());
     std::slice::from_raw_parts(ptr, len).iter().all(|b| *b == 0)
@@ -14,7 +14,7 @@ pub(crate) unsafe fn is_zeroed_ptr(ptr: *const u8, len: usize) -> bool {
 /// # Safety
 ///
 /// `ptr` needs to be non-null and initialized to the length specified in `len`.
-#[cfg(feature = "realloc")]
+#[cfg(all(feature = "realloc", not(feature = "no-ub")))]
 pub(crate) unsafe fn hash_ptr(ptr: *const u8, len: usize) -> impl Eq {
     debug_assert!(!ptr.is_null());
     fxhash::hash64(std::slice::from_raw_parts(ptr, len))
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use crate::{Realloc, ReallocNull, Region, Request};
+use crate::{Pointer, Realloc, ReallocNull, Region, Request};
 
 /// A single violation in the variants enforced by checkers.
 #[derive(Debug, Clone)]
@@ -63,10 +63,157 @@ pub enum Violation {
         /// The leaked region.
         alloc: Request,
     },
+    /// An allocation exceeded the size permitted by a
+    /// [MaxSizePolicy][crate::MaxSizePolicy].
+    OversizedAlloc {
+        /// The allocated region.
+        alloc: Request,
+        /// The maximum permitted size.
+        max_size: usize,
+    },
+    /// More allocations were performed than permitted by a
+    /// [MaxCountPolicy][crate::MaxCountPolicy].
+    TooManyAllocs {
+        /// The number of allocations performed.
+        count: usize,
+        /// The maximum permitted number of allocations.
+        max: usize,
+    },
+    /// An allocation would cause more regions to be live at the same time than
+    /// permitted by [Machine::set_max_live_regions][crate::Machine::set_max_live_regions].
+    TooManyLiveRegions {
+        /// The number of live regions including the new allocation.
+        count: usize,
+        /// The maximum permitted number of live regions.
+        max: usize,
+    },
+    /// A freed region `request` was deallocated before the allocation it
+    /// matches. See [Events::validate_timeline][crate::Events::validate_timeline].
+    OutOfOrderFree {
+        /// The index of the matched allocation event.
+        alloc_index: usize,
+        /// The index of the deallocation event.
+        free_index: usize,
+        /// The freed region.
+        request: Request,
+    },
+    /// An allocated region `alloc` doesn't lie within the address range
+    /// `[lo, hi)`. See [with_region_guard][crate::with_region_guard].
+    OutOfRegion {
+        /// The allocated region.
+        alloc: Request,
+        /// The start of the permitted range.
+        lo: Pointer,
+        /// The end of the permitted range.
+        hi: Pointer,
+    },
+    /// A region `alloc` of size zero was allocated. See
+    /// [Events::check_no_zero_size_allocs][crate::Events::check_no_zero_size_allocs].
+    ZeroSizeAlloc {
+        /// The allocated region.
+        alloc: Request,
+    },
+    /// A region `alloc` was allocated with an alignment larger than permitted.
+    /// See [Events::check_no_overaligned_allocs][crate::Events::check_no_overaligned_allocs].
+    OveralignedAlloc {
+        /// The allocated region.
+        alloc: Request,
+        /// The maximum permitted alignment.
+        max_align: usize,
+    },
+    /// A region `request` of size zero was freed. See
+    /// [Events::check_no_size_zero_frees][crate::Events::check_no_size_zero_frees].
+    ZeroSizeFree {
+        /// The freed region.
+        request: Request,
+    },
+    /// A `violation` with additional user-provided `context`. See
+    /// [Violation::with_context].
+    Context {
+        /// The context of the violation.
+        context: String,
+        /// The violation.
+        violation: Box<Violation>,
+    },
 }
 
 /// A single violation to the virtual memory model of checkers.
 impl Violation {
+    /// Construct a [Violation::Leaked] for the given allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use checkers::{Request, Region, Violation};
+    /// let violation = Violation::for_region(Request::without_backtrace(Region::new(42.into(), 20, 4)));
+    /// assert!(violation.is_leaked_with(|r| r.size == 20));
+    /// ```
+    pub fn for_region(alloc: Request) -> Self {
+        Self::Leaked { alloc }
+    }
+
+    /// Construct a [Violation::MissingFree] for the given deallocation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use checkers::{Request, Region, Violation};
+    /// let violation = Violation::missing_free(Request::without_backtrace(Region::new(42.into(), 20, 4)));
+    /// assert!(matches!(violation, Violation::MissingFree { .. }));
+    /// ```
+    pub fn missing_free(request: Request) -> Self {
+        Self::MissingFree { request }
+    }
+
+    /// Construct a [Violation::MisalignedAlloc] for the given allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use checkers::{Request, Region, Violation};
+    /// let violation = Violation::misaligned_alloc(Request::without_backtrace(Region::new(42.into(), 20, 4)));
+    /// assert!(matches!(violation, Violation::MisalignedAlloc { .. }));
+    /// ```
+    pub fn misaligned_alloc(alloc: Request) -> Self {
+        Self::MisalignedAlloc { alloc }
+    }
+
+    /// Construct a [Violation::NonZeroedAlloc] for the given allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use checkers::{Request, Region, Violation};
+    /// let violation = Violation::non_zeroed_alloc(Request::without_backtrace(Region::new(42.into(), 20, 4)));
+    /// assert!(matches!(violation, Violation::NonZeroedAlloc { .. }));
+    /// ```
+    pub fn non_zeroed_alloc(alloc: Request) -> Self {
+        Self::NonZeroedAlloc { alloc }
+    }
+
+    /// Wrap the violation with additional context, which is prepended when it
+    /// is displayed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use checkers::{Request, Region, Violation};
+    /// let alloc = Request::without_backtrace(Region::new(0x1234.into(), 16, 8));
+    /// let violation = Violation::Leaked { alloc }.with_context("in phase 2");
+    ///
+    /// assert!(matches!(violation, Violation::Context { .. }));
+    /// assert_eq!("in phase 2: Leaked: 0x1234[16B@8]", violation.summary_line());
+    /// ```
+    pub fn with_context<C>(self, context: C) -> Self
+    where
+        C: Into<String>,
+    {
+        Self::Context {
+            context: context.into(),
+            violation: Box::new(self),
+        }
+    }
+
     /// Test that this violation refers to a dangling region and that it matches
     /// the given predicate.
     ///
@@ -91,6 +238,105 @@ impl Violation {
             _ => false,
         }
     }
+
+    /// Produce a single-line summary of the violation which doesn't include
+    /// any backtraces.
+    ///
+    /// See [Region::fmt_compact] for how regions are formatted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use checkers::{Request, Region, Violation};
+    /// let alloc = Request::without_backtrace(Region::new(0x1234.into(), 16, 8));
+    /// let violation = Violation::Leaked { alloc };
+    /// assert_eq!("Leaked: 0x1234[16B@8]", violation.summary_line());
+    ///
+    /// let alloc = Request::without_backtrace(Region::new(0x1235.into(), 16, 4));
+    /// let violation = Violation::MisalignedAlloc { alloc };
+    /// assert_eq!(
+    ///     "MisalignedAlloc: 0x1235[16B@4] (not 4-byte aligned)",
+    ///     violation.summary_line()
+    /// );
+    /// ```
+    pub fn summary_line(&self) -> String {
+        match self {
+            Self::ConflictingAlloc { request, existing } => format!(
+                "ConflictingAlloc: {} (overlaps {})",
+                request.region.fmt_compact(),
+                existing.region.fmt_compact()
+            ),
+            Self::NonZeroedAlloc { alloc } => {
+                format!("NonZeroedAlloc: {}", alloc.region.fmt_compact())
+            }
+            Self::NonCopiedRealloc { realloc } => format!(
+                "NonCopiedRealloc: {} -> {}",
+                realloc.free.fmt_compact(),
+                realloc.alloc.fmt_compact()
+            ),
+            Self::ReallocNull { .. } => String::from("ReallocNull"),
+            Self::MisalignedAlloc { alloc } => format!(
+                "MisalignedAlloc: {} (not {}-byte aligned)",
+                alloc.region.fmt_compact(),
+                alloc.region.align
+            ),
+            Self::IncompleteFree { request, existing } => format!(
+                "IncompleteFree: {} (part of {})",
+                request.region.fmt_compact(),
+                existing.region.fmt_compact()
+            ),
+            Self::MisalignedFree { request, existing } => format!(
+                "MisalignedFree: {} (allocated with {}-byte alignment)",
+                request.region.fmt_compact(),
+                existing.region.align
+            ),
+            Self::MissingFree { request } => {
+                format!("MissingFree: {}", request.region.fmt_compact())
+            }
+            Self::Leaked { alloc } => format!("Leaked: {}", alloc.region.fmt_compact()),
+            Self::OversizedAlloc { alloc, max_size } => format!(
+                "OversizedAlloc: {} (max {}B)",
+                alloc.region.fmt_compact(),
+                max_size
+            ),
+            Self::TooManyAllocs { count, max } => {
+                format!("TooManyAllocs: {} (max {})", count, max)
+            }
+            Self::TooManyLiveRegions { count, max } => {
+                format!("TooManyLiveRegions: {} (max {})", count, max)
+            }
+            Self::OutOfOrderFree {
+                alloc_index,
+                free_index,
+                request,
+            } => format!(
+                "OutOfOrderFree: {} (freed at {}, allocated at {})",
+                request.region.fmt_compact(),
+                free_index,
+                alloc_index
+            ),
+            Self::OutOfRegion { alloc, lo, hi } => format!(
+                "OutOfRegion: {} (outside {:#x}-{:#x})",
+                alloc.region.fmt_compact(),
+                lo,
+                hi
+            ),
+            Self::ZeroSizeAlloc { alloc } => {
+                format!("ZeroSizeAlloc: {}", alloc.region.fmt_compact())
+            }
+            Self::OveralignedAlloc { alloc, max_align } => format!(
+                "OveralignedAlloc: {} (max align {})",
+                alloc.region.fmt_compact(),
+                max_align
+            ),
+            Self::ZeroSizeFree { request } => {
+                format!("ZeroSizeFree: {}", request.region.fmt_compact())
+            }
+            Self::Context { context, violation } => {
+                format!("{}: {}", context, violation.summary_line())
+            }
+        }
+    }
 }
 
 impl fmt::Display for Violation {
@@ -221,6 +467,101 @@ impl fmt::Display for Violation {
 
                 Ok(())
             }
+            Self::OversizedAlloc { alloc, max_size } => {
+                write!(
+                    f,
+                    "Allocated region ({}) exceeds the maximum size of {} bytes",
+                    alloc.region, max_size
+                )?;
+
+                if let Some(bt) = &alloc.backtrace {
+                    writeln!(f)?;
+                    write!(f, "Backtrace: {:?}", bt)?;
+                }
+
+                Ok(())
+            }
+            Self::TooManyAllocs { count, max } => {
+                write!(
+                    f,
+                    "Performed {} allocations, exceeding the maximum of {}",
+                    count, max
+                )
+            }
+            Self::TooManyLiveRegions { count, max } => {
+                write!(
+                    f,
+                    "Allocation would result in {} live regions, exceeding the maximum of {}",
+                    count, max
+                )
+            }
+            Self::OutOfOrderFree {
+                alloc_index,
+                free_index,
+                request,
+            } => {
+                write!(
+                    f,
+                    "Freed region ({}) at event {} before it was allocated at event {}",
+                    request.region, free_index, alloc_index
+                )?;
+
+                if let Some(bt) = &request.backtrace {
+                    writeln!(f)?;
+                    write!(f, "Backtrace: {:?}", bt)?;
+                }
+
+                Ok(())
+            }
+            Self::OutOfRegion { alloc, lo, hi } => {
+                write!(
+                    f,
+                    "Allocated region ({}) is outside of the permitted range {}-{}",
+                    alloc.region, lo, hi
+                )?;
+
+                if let Some(bt) = &alloc.backtrace {
+                    writeln!(f)?;
+                    write!(f, "Backtrace: {:?}", bt)?;
+                }
+
+                Ok(())
+            }
+            Self::ZeroSizeAlloc { alloc } => {
+                write!(f, "Allocated region ({}) is zero-sized", alloc.region)?;
+
+                if let Some(bt) = &alloc.backtrace {
+                    writeln!(f)?;
+                    write!(f, "Backtrace: {:?}", bt)?;
+                }
+
+                Ok(())
+            }
+            Self::OveralignedAlloc { alloc, max_align } => {
+                write!(
+                    f,
+                    "Allocated region ({}) exceeds the maximum alignment of {} bytes",
+                    alloc.region, max_align
+                )?;
+
+                if let Some(bt) = &alloc.backtrace {
+                    writeln!(f)?;
+                    write!(f, "Backtrace: {:?}", bt)?;
+                }
+
+                Ok(())
+            }
+            Self::ZeroSizeFree { request } => {
+                write!(f, "Freed region ({}) is zero-sized", request.region)?;
+
+                if let Some(bt) = &request.backtrace {
+                    writeln!(f)?;
+                    write!(f, "Backtrace: {:?}", bt)?;
+                }
+
+                Ok(())
+            }
+            Self::Context { context, violation } => write!(f, "{}: {}", context, violation),
         }
     }
 }
@@ -0,0 +1,64 @@
+#[global_allocator]
+static ALLOCATOR: checkers::Allocator = checkers::Allocator::system();
+
+/// Note: minimal stand-in for `criterion::Bencher`.
+struct Bencher;
+
+impl Bencher {
+    fn iter<O, R>(&mut self, mut routine: R)
+    where
+        R: FnMut() -> O,
+    {
+        for _ in 0..10 {
+            routine();
+        }
+    }
+}
+
+#[checkers::bench(allocs = 1)]
+fn bench_single_alloc(b: &mut Bencher) {
+    let x = Box::new(0u128);
+    // Prevent optimization in `--release`
+    unsafe {
+        std::ptr::read_volatile(&*x);
+    }
+}
+
+#[checkers::bench(allocs = 2)]
+fn bench_unexpected_allocs(b: &mut Bencher) {
+    let _ = Box::new(0u128);
+}
+
+#[checkers::bench]
+fn bench_leak(b: &mut Bencher) {
+    let x = Box::into_raw(Box::new(0u128));
+    // Prevent optimization in `--release`
+    unsafe {
+        std::ptr::write_volatile(x, 1u128);
+    }
+}
+
+#[test]
+fn test_bench_single_alloc() {
+    bench_single_alloc(&mut Bencher);
+}
+
+/// Note: only the verified run is recorded, the timed iterations are muted.
+#[test]
+fn test_bench_iterations_are_not_recorded() {
+    bench_single_alloc(&mut Bencher);
+    let events = checkers::with_state(|s| s.borrow().events.len());
+    assert_eq!(2, events);
+}
+
+#[test]
+#[should_panic = "expected 2 allocations per iteration, but got 1"]
+fn test_bench_unexpected_allocs() {
+    bench_unexpected_allocs(&mut Bencher);
+}
+
+#[test]
+#[should_panic = "allocation checks failed"]
+fn test_bench_leak() {
+    bench_leak(&mut Bencher);
+}
@@ -14,3 +14,154 @@ fn events_test() {
 
     assert_eq!(800, events.as_slice().len());
 }
+
+#[cfg(feature = "parallel")]
+#[test]
+fn validate_parallel_test() {
+    use checkers::Realloc;
+
+    let request =
+        |ptr: usize, size: usize| Request::without_backtrace(Region::new(ptr.into(), size, 1));
+
+    let mut events = Events::new();
+
+    for n in 0..100 {
+        let base = n * 0x100;
+        events.push(Event::Alloc(request(base, 0x10)));
+        // Note: conflicts with the previous allocation.
+        events.push(Event::Alloc(request(base + 0x8, 0x10)));
+        events.push(Event::Realloc(Realloc::without_backtrace(
+            Some(true),
+            Region::new(base.into(), 0x10, 1),
+            Region::new((base + 0x80).into(), 0x20, 1),
+        )));
+
+        if n % 2 == 0 {
+            events.push(Event::Free(request(base + 0x80, 0x20)));
+        }
+
+        if n % 3 == 0 {
+            events.push(Event::Free(request(base + 0x80, 0x10)));
+        }
+    }
+
+    let mut expected = Vec::new();
+    events.validate(&mut expected);
+
+    let mut actual = Vec::new();
+    events.validate_parallel(&mut actual);
+
+    let expected = expected
+        .iter()
+        .map(|e| e.summary_line())
+        .collect::<Vec<_>>();
+    let actual = actual.iter().map(|e| e.summary_line()).collect::<Vec<_>>();
+    assert!(!expected.is_empty());
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn time_to_first_free_realloc_test() {
+    use checkers::Realloc;
+
+    let mut events = Events::new();
+
+    events.push(Event::Alloc(Request::without_backtrace(Region::new(
+        0x10.into(),
+        0x10,
+        1,
+    ))));
+    events.push(Event::Alloc(Request::without_backtrace(Region::new(
+        0x20.into(),
+        0x10,
+        1,
+    ))));
+    events.push(Event::Realloc(Realloc::without_backtrace(
+        Some(true),
+        Region::new(0x10.into(), 0x10, 1),
+        Region::new(0x40.into(), 0x20, 1),
+    )));
+    events.push(Event::Free(Request::without_backtrace(Region::new(
+        0x40.into(),
+        0x20,
+        1,
+    ))));
+
+    assert_eq!(Some(2), events.time_to_first_free());
+}
+
+#[test]
+fn allocation_profile_huge_size_test() {
+    let mut events = Events::new();
+
+    events.push(Event::Alloc(Request::without_backtrace(Region::new(
+        0x10.into(),
+        usize::MAX,
+        1,
+    ))));
+
+    let profile = events.allocation_profile();
+    assert_eq!(Some(&1), profile.size_buckets.get(&usize::MAX));
+}
+
+#[test]
+fn region_guard_entry_points_test() {
+    use checkers::Violation;
+    use std::collections::HashSet;
+
+    let request = Request::without_backtrace(Region::new(0x10.into(), 0x10, 1));
+
+    let mut events = Events::new();
+    events.push(Event::Alloc(request.clone()));
+    events.push(Event::Free(request));
+    events.set_region_guard(0x20.into(), 0x40.into());
+
+    let is_out_of_region = |errors: &[Violation]| {
+        errors.len() == 1 && matches!(errors[0], Violation::OutOfRegion { .. })
+    };
+
+    let mut errors = Vec::new();
+    events.validate(&mut errors);
+    assert!(is_out_of_region(&errors));
+
+    let mut errors = Vec::new();
+    events.validate_with_ignored_ptrs(&HashSet::new(), &mut errors);
+    assert!(is_out_of_region(&errors));
+
+    let mut errors = Vec::new();
+    events.snapshot_at(events.len()).validate(&mut errors);
+    assert!(is_out_of_region(&errors));
+
+    #[cfg(feature = "parallel")]
+    {
+        let mut errors = Vec::new();
+        events.validate_parallel(&mut errors);
+        assert!(is_out_of_region(&errors));
+    }
+
+    assert!(matches!(
+        events.first_violation(),
+        Some(Violation::OutOfRegion { .. })
+    ));
+    assert!(is_out_of_region(&events.clone().into_report().violations));
+}
+
+#[test]
+fn time_to_first_free_leading_realloc_test() {
+    use checkers::Realloc;
+
+    let mut events = Events::new();
+
+    events.push(Event::Realloc(Realloc::without_backtrace(
+        Some(true),
+        Region::new(0x10.into(), 0x10, 1),
+        Region::new(0x40.into(), 0x20, 1),
+    )));
+    events.push(Event::Free(Request::without_backtrace(Region::new(
+        0x40.into(),
+        0x20,
+        1,
+    ))));
+
+    assert_eq!(Some(0), events.time_to_first_free());
+}
@@ -32,3 +32,28 @@ fn test_custom_verify() {
         std::ptr::write_volatile(x as _, 6);
     }
 }
+
+#[checkers::test(allow_leaks = true)]
+fn test_allow_leaks() {
+    let x = Box::into_raw(Box::new(0u128));
+    // Prevent optimization in `--release`
+    unsafe {
+        std::ptr::write_volatile(x, 1u128);
+    }
+}
+
+#[checkers::test(allow_leaks = true)]
+#[should_panic]
+fn test_allow_leaks_missing_free() {
+    use checkers::{Event, Region, Request};
+
+    // Note: a free of a region which was never allocated is still reported.
+    checkers::with_muted(|| {
+        checkers::with_state(|s| {
+            let region = Region::new(0x10.into(), 0x10, 1);
+            s.borrow_mut()
+                .events
+                .push(Event::Free(Request::without_backtrace(region)));
+        });
+    });
+}
@@ -0,0 +1,56 @@
+use checkers::Pointer;
+
+#[global_allocator]
+static ALLOCATOR: checkers::Allocator = checkers::Allocator::system();
+
+/// Note: with the `backtrace` feature enabled, rejected events carry a
+/// backtrace which is deallocated when they are dropped.
+#[test]
+fn test_limit_rejects_events() {
+    let snapshot = checkers::with_limit(4, || {
+        for n in 0..100 {
+            let _ = Box::new(n);
+            let _ = vec![n; n];
+        }
+    });
+
+    assert_eq!(4, snapshot.events.len());
+    assert!(snapshot.is_truncated());
+
+    let snapshot = checkers::with(|| {
+        let _ = Box::new(42);
+    });
+
+    assert_eq!(2, snapshot.events.len());
+    assert!(!snapshot.is_truncated());
+}
+
+#[test]
+fn test_limit_restored_after_panic() {
+    let result = std::panic::catch_unwind(|| {
+        checkers::with_limit(1, || panic!("boom"));
+    });
+
+    assert!(result.is_err());
+
+    let snapshot = checkers::with(|| {
+        let _ = Box::new(42);
+    });
+
+    assert_eq!(2, snapshot.events.len());
+    assert!(!snapshot.is_truncated());
+}
+
+#[test]
+fn test_limit_truncated_is_kept() {
+    let snapshot = checkers::with_limit(1, || {
+        let _ = Box::new(42);
+    });
+
+    assert!(snapshot.is_truncated());
+    assert!(snapshot.events.snapshot_at(1).is_truncated());
+
+    let events = snapshot.events_in_range(Pointer::from(0), Pointer::from(usize::MAX));
+    assert_eq!(1, events.len());
+    assert!(events.is_truncated());
+}
@@ -0,0 +1,43 @@
+#[global_allocator]
+static ALLOCATOR: checkers::Allocator = checkers::Allocator::system();
+
+#[checkers::test]
+fn test_inner() {
+    let b = Box::new(0u128);
+    let _ = unsafe { std::ptr::read_volatile(&*b) };
+}
+
+#[checkers::test]
+fn test_outer() {
+    // Note: would be reported as a missing free if the inner test cleared the
+    // state collected so far.
+    let b = Box::new(0u128);
+    test_inner();
+    drop(b);
+}
+
+#[checkers::test]
+#[should_panic]
+fn test_outer_leak() {
+    let x = Box::into_raw(Box::new(0u128));
+    test_inner();
+    // Prevent optimization in `--release`
+    unsafe {
+        std::ptr::write_volatile(x, 1u128);
+    }
+}
+
+#[checkers::test]
+fn test_inner_result() -> Result<(), std::num::ParseIntError> {
+    let b = Box::new("42".parse::<u32>()?);
+    let _ = unsafe { std::ptr::read_volatile(&*b) };
+    Ok(())
+}
+
+#[checkers::test]
+fn test_outer_result() -> Result<(), std::num::ParseIntError> {
+    let b = Box::new(0u128);
+    test_inner_result()?;
+    drop(b);
+    Ok(())
+}
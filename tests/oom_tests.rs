@@ -0,0 +1,96 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::env;
+use std::process::Command;
+use std::ptr;
+
+use checkers::{Allocator, OomPolicy};
+
+#[global_allocator]
+static ALLOCATOR: Allocator = Allocator::system();
+
+/// Note: allocator which always fails to allocate.
+struct TestAllocator;
+
+unsafe impl GlobalAlloc for TestAllocator {
+    unsafe fn alloc(&self, _: Layout) -> *mut u8 {
+        ptr::null_mut()
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+static RETURN_NULL: Allocator<TestAllocator> = Allocator::new(TestAllocator);
+static HANDLE_ALLOC_ERROR: Allocator<TestAllocator> =
+    Allocator::new(TestAllocator).with_oom_policy(OomPolicy::HandleAllocError);
+
+#[test]
+fn test_oom_return_null() {
+    let layout = Layout::from_size_align(10, 1).unwrap();
+
+    let snapshot = checkers::with(|| unsafe {
+        assert_eq!(ptr::null_mut(), RETURN_NULL.alloc(layout));
+    });
+
+    assert_eq!(1, snapshot.events.len());
+    assert!(snapshot.events[0].is_failed());
+}
+
+/// Run the given test in a child process, returning its stderr after
+/// asserting that it failed.
+fn run_child(test: &str) -> String {
+    let output = Command::new(env::current_exe().unwrap())
+        .arg("--exact")
+        .arg(test)
+        .env("CHECKERS_OOM_CHILD", "1")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// Note: this policy aborts the process, so the allocation happens in a child
+/// process running this test again.
+#[test]
+fn test_oom_handle_alloc_error() {
+    if env::var_os("CHECKERS_OOM_CHILD").is_some() {
+        let layout = Layout::from_size_align(10, 2).unwrap();
+
+        unsafe {
+            HANDLE_ALLOC_ERROR.alloc(layout);
+        }
+
+        return;
+    }
+
+    let stderr = run_child("test_oom_handle_alloc_error");
+    assert!(
+        stderr.contains("failed to allocate 10 bytes with alignment 2"),
+        "unexpected stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_oom_handle_alloc_error_muted_realloc() {
+    if env::var_os("CHECKERS_OOM_CHILD").is_some() {
+        let layout = Layout::from_size_align(10, 2).unwrap();
+
+        unsafe {
+            let ptr = System.alloc(layout);
+            assert!(!ptr.is_null());
+            HANDLE_ALLOC_ERROR.realloc(ptr, layout, 20);
+        }
+
+        return;
+    }
+
+    let stderr = run_child("test_oom_handle_alloc_error_muted_realloc");
+    assert!(
+        stderr.contains("failed to allocate 20 bytes with alignment 2"),
+        "unexpected stderr: {}",
+        stderr
+    );
+}
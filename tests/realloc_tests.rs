@@ -29,7 +29,7 @@ unsafe impl GlobalAlloc for TestAllocator {
 #[global_allocator]
 static ALLOCATOR: checkers::Allocator<TestAllocator> = checkers::Allocator::new(TestAllocator);
 
-#[cfg(feature = "realloc")]
+#[cfg(all(feature = "realloc", not(feature = "no-ub")))]
 #[test]
 fn test_realloc() {
     let snapshot = checkers::with(|| {
@@ -48,5 +48,8 @@ fn test_realloc() {
         // Note: not correctly relocated since we corrupted the third byte.
         r.is_relocated == Some(false) && r.free.size == 4 && r.alloc.size == 16
     }));
+    assert!(snapshot.events[1].is_realloc_with(|r| {
+        r.original_layout().map(|l| (l.size(), l.align())) == Some((4, 4))
+    }));
     assert!(snapshot.events[2].is_free_with(|r| r.size == 16));
 }
@@ -0,0 +1,36 @@
+#![cfg(feature = "testing")]
+
+use checkers::testing::MockAllocator;
+use checkers::{Event::*, Events, Pointer, Violation};
+
+#[test]
+fn test_mock_allocator_regions() {
+    let mut allocator = MockAllocator::new();
+
+    let a = allocator.alloc_region(0, 1);
+    let b = allocator.alloc_region(3, 1);
+    let c = allocator.alloc_region(0x10, 16);
+
+    assert_eq!(Pointer::from(1), a.ptr);
+    assert_eq!(Pointer::from(2), b.ptr);
+    assert_eq!(Pointer::from(16), c.ptr);
+}
+
+#[test]
+fn test_mock_allocator_validate() {
+    let mut allocator = MockAllocator::new();
+    let mut events = Events::new();
+
+    let a = allocator.alloc_request(0x10, 8);
+    let b = allocator.alloc_request(0x20, 4);
+
+    events.push(Alloc(a.clone()));
+    events.push(Alloc(b));
+    events.push(Free(a));
+
+    let mut errors = Vec::new();
+    events.validate(&mut errors);
+
+    assert_eq!(1, errors.len());
+    assert!(matches!(errors[0], Violation::Leaked { .. }));
+}
@@ -26,7 +26,7 @@ unsafe impl GlobalAlloc for TestAllocator {
 #[global_allocator]
 static ALLOCATOR: checkers::Allocator<TestAllocator> = checkers::Allocator::new(TestAllocator);
 
-#[cfg(feature = "realloc")]
+#[cfg(all(feature = "realloc", not(feature = "no-ub")))]
 #[test]
 fn test_realloc() {
     let snapshot = checkers::with(|| {